@@ -0,0 +1,250 @@
+use crate::{app::FirebaseApp, FirebaseError, SERIALIZATION_ERROR_CODE};
+use serde::Serialize;
+use std::{error::Error, fmt};
+use wasm_bindgen::prelude::*;
+
+#[derive(Clone, Debug, derive_more::Deref)]
+pub struct AnalyticsError {
+    pub kind: AnalyticsErrorKind,
+    #[deref]
+    pub source: FirebaseError,
+}
+
+impl fmt::Display for AnalyticsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl Error for AnalyticsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl AnalyticsError {
+    pub(crate) fn serialization(err: serde_wasm_bindgen::Error) -> Self {
+        Self {
+            kind: AnalyticsErrorKind::Serialization,
+            source: FirebaseError::from_serde_error(err, SERIALIZATION_ERROR_CODE),
+        }
+    }
+}
+
+impl From<FirebaseError> for AnalyticsError {
+    fn from(err: FirebaseError) -> Self {
+        let code = err.code();
+        let kind = code
+            .parse()
+            .unwrap_or_else(|_| AnalyticsErrorKind::Other(code));
+
+        Self { kind, source: err }
+    }
+}
+
+#[derive(Clone, Debug, strum_macros::EnumString)]
+#[non_exhaustive]
+pub enum AnalyticsErrorKind {
+    #[strum(serialize = "analytics/already-exists")]
+    AlreadyExists,
+    #[strum(serialize = "analytics/already-initialized")]
+    AlreadyInitialized,
+    #[strum(serialize = "analytics/invalid-analytics-context")]
+    InvalidAnalyticsContext,
+    #[strum(serialize = "analytics/indexeddb-unavailable")]
+    IndexedDbUnavailable,
+    #[strum(serialize = "analytics/fetch-throttle")]
+    FetchThrottle,
+    #[strum(serialize = "analytics/config-fetch-failed")]
+    ConfigFetchFailed,
+    #[strum(serialize = "analytics/no-api-key")]
+    NoApiKey,
+    #[strum(serialize = "analytics/no-app-id")]
+    NoAppId,
+    /// Event parameters could not be converted to a JS value.
+    #[strum(serialize = "firebase-wasm/serialization")]
+    Serialization,
+    #[strum(default)]
+    Other(String),
+}
+
+/// Logs the event `name` with `params`, which should serialize to an
+/// object, or `()` for none.
+///
+/// Events are sent in the background, so failures are not reported.
+/// Prefer the typed helpers, such as [`log_purchase`], for the events
+/// that Analytics recommends.
+pub fn log_event<P: Serialize + ?Sized>(
+    analytics: &Analytics,
+    name: &str,
+    params: &P,
+) -> Result<(), AnalyticsError> {
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+
+    let params = params
+        .serialize(&serializer)
+        .map_err(AnalyticsError::serialization)?;
+
+    log_event_js(analytics, name, params);
+
+    Ok(())
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Clone, Debug, Default, PartialEq, TypedBuilder, Serialize)]
+#[builder(field_defaults(default, setter(strip_option, into)))]
+pub struct Item {
+    pub item_id: Option<String>,
+    pub item_name: Option<String>,
+    pub item_category: Option<String>,
+    pub price: Option<f64>,
+    pub quantity: Option<u32>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, TypedBuilder, Serialize)]
+#[builder(field_defaults(default, setter(strip_option, into)))]
+pub struct PurchaseParams {
+    #[builder(!default, setter(!strip_option))]
+    pub transaction_id: String,
+    /// The ISO 4217 currency code, such as `"USD"`, which is required when
+    /// `value` is set.
+    pub currency: Option<String>,
+    pub value: Option<f64>,
+    pub tax: Option<f64>,
+    pub shipping: Option<f64>,
+    pub coupon: Option<String>,
+    #[builder(setter(!strip_option))]
+    pub items: Vec<Item>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, TypedBuilder, Serialize)]
+#[builder(field_defaults(default, setter(strip_option, into)))]
+pub struct ScreenViewParams {
+    #[builder(!default, setter(!strip_option))]
+    pub firebase_screen: String,
+    pub firebase_screen_class: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, TypedBuilder, Serialize)]
+pub struct SearchParams {
+    #[builder(setter(into))]
+    pub search_term: String,
+}
+
+/// Logs the recommended `purchase` event.
+pub fn log_purchase(analytics: &Analytics, params: &PurchaseParams) -> Result<(), AnalyticsError> {
+    log_event(analytics, "purchase", params)
+}
+
+/// Logs the recommended `screen_view` event, such as when the route of a
+/// single-page app changes.
+pub fn log_screen_view(
+    analytics: &Analytics,
+    params: &ScreenViewParams,
+) -> Result<(), AnalyticsError> {
+    log_event(analytics, "screen_view", params)
+}
+
+/// Logs the recommended `search` event.
+pub fn log_search(analytics: &Analytics, params: &SearchParams) -> Result<(), AnalyticsError> {
+    log_event(analytics, "search", params)
+}
+
+/// Sets the ID that future events are attributed to, or clears it when
+/// `None`, such as on sign-out.
+pub fn set_user_id(analytics: &Analytics, id: Option<&str>) {
+    set_user_id_js(analytics, id.map(JsValue::from).unwrap_or(JsValue::NULL));
+}
+
+/// Sets the user properties in `properties`, which should serialize to an
+/// object, for future events.
+pub fn set_user_properties<P: Serialize + ?Sized>(
+    analytics: &Analytics,
+    properties: &P,
+) -> Result<(), AnalyticsError> {
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+
+    let properties = properties
+        .serialize(&serializer)
+        .map_err(AnalyticsError::serialization)?;
+
+    set_user_properties_js(analytics, properties);
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsentStatus {
+    Granted,
+    Denied,
+}
+
+/// Consent for each kind of storage. Kinds left as `None` keep their
+/// current status.
+#[serde_with::skip_serializing_none]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, TypedBuilder, Serialize)]
+#[builder(field_defaults(default, setter(strip_option)))]
+pub struct ConsentSettings {
+    pub ad_storage: Option<ConsentStatus>,
+    pub ad_user_data: Option<ConsentStatus>,
+    pub ad_personalization: Option<ConsentStatus>,
+    pub analytics_storage: Option<ConsentStatus>,
+    pub functionality_storage: Option<ConsentStatus>,
+    pub personalization_storage: Option<ConsentStatus>,
+    pub security_storage: Option<ConsentStatus>,
+}
+
+/// Sets the user's consent, such as from a cookie banner. Applies to every
+/// [`Analytics`] instance, and should be called before [`get_analytics`]
+/// for the initial consent.
+pub fn set_consent(consent: &ConsentSettings) -> Result<(), AnalyticsError> {
+    let consent = serde_wasm_bindgen::to_value(consent).map_err(AnalyticsError::serialization)?;
+
+    set_consent_js(consent);
+
+    Ok(())
+}
+
+/// Whether analytics can run in this environment, which requires a browser
+/// with cookies and IndexedDB enabled.
+pub async fn is_supported() -> bool {
+    is_supported_js()
+        .await
+        .ok()
+        .and_then(|supported| supported.as_bool())
+        .unwrap_or_default()
+}
+
+#[wasm_bindgen(module = "firebase/analytics")]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type Analytics;
+
+    /// Returns the [`Analytics`] instance of `app`, or of the default app
+    /// when `None`.
+    #[wasm_bindgen(js_name = getAnalytics)]
+    pub fn get_analytics(app: Option<&FirebaseApp>) -> Analytics;
+
+    #[wasm_bindgen(js_name = logEvent)]
+    fn log_event_js(analytics: &Analytics, name: &str, params: JsValue);
+
+    #[wasm_bindgen(js_name = setUserId)]
+    fn set_user_id_js(analytics: &Analytics, id: JsValue);
+
+    #[wasm_bindgen(js_name = setUserProperties)]
+    fn set_user_properties_js(analytics: &Analytics, properties: JsValue);
+
+    /// Enables or disables collection on this device, such as for a "do not
+    /// track" setting.
+    #[wasm_bindgen(js_name = setAnalyticsCollectionEnabled)]
+    pub fn set_analytics_collection_enabled(analytics: &Analytics, enabled: bool);
+
+    #[wasm_bindgen(js_name = setConsent)]
+    fn set_consent_js(consent: JsValue);
+
+    #[wasm_bindgen(js_name = isSupported, catch)]
+    async fn is_supported_js() -> Result<JsValue, JsValue>;
+}
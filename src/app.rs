@@ -0,0 +1,85 @@
+use crate::{FirebaseError, SERIALIZATION_ERROR_CODE};
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Eq, TypedBuilder, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(setter(into)))]
+pub struct FirebaseConfig {
+    pub api_key: String,
+    pub auth_domain: String,
+    pub project_id: String,
+    pub storage_bucket: String,
+    pub messaging_sender_id: String,
+    pub app_id: String,
+    #[builder(default, setter(strip_option))]
+    pub measurement_id: Option<String>,
+    #[builder(default, setter(strip_option))]
+    #[serde(rename = "databaseURL")]
+    pub database_url: Option<String>,
+}
+
+/// Initializes the app named `name`, or the default app when `None`.
+pub fn initialize_app(
+    config: &FirebaseConfig,
+    name: Option<&str>,
+) -> Result<FirebaseApp, FirebaseError> {
+    let config = serde_wasm_bindgen::to_value(config)
+        .map_err(|err| FirebaseError::from_serde_error(err, SERIALIZATION_ERROR_CODE))?;
+
+    initialize_app_js(config, name)
+}
+
+/// Fails with the `app/no-app` code if the app has not been initialized.
+pub fn get_app(name: Option<&str>) -> Result<FirebaseApp, FirebaseError> {
+    get_app_js(name)
+}
+
+pub fn get_apps() -> Vec<FirebaseApp> {
+    get_apps_js()
+        .iter()
+        .map(JsCast::unchecked_into::<FirebaseApp>)
+        .collect()
+}
+
+/// Deletes the app and frees its resources, allowing an app with the same
+/// name to be initialized again.
+pub async fn delete_app(app: &FirebaseApp) -> Result<(), FirebaseError> {
+    delete_app_js(app)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>())
+}
+
+#[wasm_bindgen(module = "firebase/app")]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type FirebaseApp;
+
+    #[wasm_bindgen(js_name = initializeApp, catch)]
+    fn initialize_app_js(
+        options: JsValue,
+        name: Option<&str>,
+    ) -> Result<FirebaseApp, FirebaseError>;
+
+    #[wasm_bindgen(js_name = getApp, catch)]
+    fn get_app_js(name: Option<&str>) -> Result<FirebaseApp, FirebaseError>;
+
+    #[wasm_bindgen(js_name = getApps)]
+    fn get_apps_js() -> js_sys::Array;
+
+    #[wasm_bindgen(js_name = deleteApp, catch)]
+    async fn delete_app_js(app: &FirebaseApp) -> Result<(), JsValue>;
+
+    // =========================================================================
+    //                              FirebaseApp
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter)]
+    pub fn name(this: &FirebaseApp) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn options(this: &FirebaseApp) -> js_sys::Object;
+
+    #[wasm_bindgen(method, getter, js_name = automaticDataCollectionEnabled)]
+    pub fn automatic_data_collection_enabled(this: &FirebaseApp) -> bool;
+}
@@ -0,0 +1,179 @@
+use crate::{app::FirebaseApp, stream::callback_stream, FirebaseError};
+use futures::Stream;
+use std::{error::Error, fmt};
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[derive(Clone, Debug, derive_more::Deref)]
+pub struct AppCheckError {
+    pub kind: AppCheckErrorKind,
+    #[deref]
+    pub source: FirebaseError,
+}
+
+impl fmt::Display for AppCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl Error for AppCheckError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<FirebaseError> for AppCheckError {
+    fn from(err: FirebaseError) -> Self {
+        let code = err.code();
+        let kind = code
+            .parse()
+            .unwrap_or_else(|_| AppCheckErrorKind::Other(code));
+
+        Self { kind, source: err }
+    }
+}
+
+#[derive(Clone, Debug, strum_macros::EnumString)]
+#[non_exhaustive]
+pub enum AppCheckErrorKind {
+    #[strum(serialize = "appCheck/already-initialized")]
+    AlreadyInitialized,
+    #[strum(serialize = "appCheck/use-before-activation")]
+    UseBeforeActivation,
+    #[strum(serialize = "appCheck/fetch-network-error")]
+    FetchNetworkError,
+    #[strum(serialize = "appCheck/fetch-status-error")]
+    FetchStatusError,
+    #[strum(serialize = "appCheck/recaptcha-error")]
+    RecaptchaError,
+    /// Requests were throttled after repeated failures.
+    #[strum(serialize = "appCheck/throttled")]
+    Throttled,
+    #[strum(default)]
+    Other(String),
+}
+
+/// Attests that requests come from this app.
+#[derive(Clone, Debug, derive_more::From)]
+pub enum AppCheckProvider {
+    ReCaptchaV3(ReCaptchaV3Provider),
+    ReCaptchaEnterprise(ReCaptchaEnterpriseProvider),
+}
+
+impl AsRef<JsValue> for AppCheckProvider {
+    fn as_ref(&self) -> &JsValue {
+        match self {
+            Self::ReCaptchaV3(provider) => provider.as_ref(),
+            Self::ReCaptchaEnterprise(provider) => provider.as_ref(),
+        }
+    }
+}
+
+/// Activates App Check for `app`, which must be done before using any
+/// service that enforces it, such as Firestore.
+///
+/// When `is_token_auto_refresh_enabled`, tokens are refreshed in the
+/// background as needed.
+pub fn initialize_app_check(
+    app: Option<&FirebaseApp>,
+    provider: impl Into<AppCheckProvider>,
+    is_token_auto_refresh_enabled: bool,
+) -> Result<AppCheck, AppCheckError> {
+    let provider: AppCheckProvider = provider.into();
+
+    let options = js_sys::Object::new();
+
+    js_sys::Reflect::set(&options, &"provider".into(), provider.as_ref()).unwrap();
+    js_sys::Reflect::set(
+        &options,
+        &"isTokenAutoRefreshEnabled".into(),
+        &is_token_auto_refresh_enabled.into(),
+    )
+    .unwrap();
+
+    initialize_app_check_js(app, options.into()).map_err(Into::into)
+}
+
+/// Returns the current token, fetching a new one if it expired, or if
+/// `force_refresh`.
+pub async fn get_token(
+    app_check: &AppCheck,
+    force_refresh: bool,
+) -> Result<AppCheckTokenResult, AppCheckError> {
+    get_token_js(app_check, force_refresh)
+        .await
+        .map(|result| result.unchecked_into())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Makes App Check use the debug provider, such as during local
+/// development, where reCAPTCHA attestation fails.
+///
+/// With `None`, a debug token is generated and logged to the console, to be
+/// registered in the Firebase console. Otherwise `token` is used, such as a
+/// token registered for CI. Must be called before [`initialize_app_check`].
+pub fn enable_app_check_debug_token(token: Option<&str>) {
+    let token = token.map(JsValue::from).unwrap_or(JsValue::TRUE);
+
+    js_sys::Reflect::set(
+        &js_sys::global(),
+        &"FIREBASE_APPCHECK_DEBUG_TOKEN".into(),
+        &token,
+    )
+    .unwrap();
+}
+
+/// Stream of new tokens, which stops listening when dropped.
+pub fn on_token_changed_stream(app_check: &AppCheck) -> impl Stream<Item = AppCheckTokenResult> {
+    callback_stream(
+        |on_next| on_token_changed(app_check, on_next),
+        |token| token,
+    )
+}
+
+#[wasm_bindgen(module = "firebase/app-check")]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type AppCheck;
+    #[derive(Clone, Debug)]
+    pub type AppCheckTokenResult;
+    #[derive(Clone, Debug)]
+    pub type ReCaptchaV3Provider;
+    #[derive(Clone, Debug)]
+    pub type ReCaptchaEnterpriseProvider;
+
+    #[wasm_bindgen(js_name = initializeAppCheck, catch)]
+    fn initialize_app_check_js(
+        app: Option<&FirebaseApp>,
+        options: JsValue,
+    ) -> Result<AppCheck, FirebaseError>;
+
+    #[wasm_bindgen(js_name = onTokenChanged)]
+    fn on_token_changed(
+        app_check: &AppCheck,
+        on_next: &Closure<dyn FnMut(AppCheckTokenResult)>,
+    ) -> js_sys::Function;
+
+    #[wasm_bindgen(js_name = getToken, catch)]
+    async fn get_token_js(app_check: &AppCheck, force_refresh: bool) -> Result<JsValue, JsValue>;
+
+    // =========================================================================
+    //                          AppCheckTokenResult
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter)]
+    pub fn token(this: &AppCheckTokenResult) -> String;
+
+    // =========================================================================
+    //                               Providers
+    // =========================================================================
+
+    /// `site_key` is the reCAPTCHA v3 site key registered for the app.
+    #[wasm_bindgen(constructor)]
+    pub fn new(site_key: &str) -> ReCaptchaV3Provider;
+
+    /// `site_key` is the reCAPTCHA Enterprise site key registered for the
+    /// app.
+    #[wasm_bindgen(constructor)]
+    pub fn new(site_key: &str) -> ReCaptchaEnterpriseProvider;
+}
@@ -1,6 +1,15 @@
+mod multi_factor;
+mod phone;
+mod provider;
 mod user;
 
-use crate::FirebaseError;
+use crate::{
+    app::FirebaseApp, stream::callback_stream, FirebaseError, Unsubscribe, SERIALIZATION_ERROR_CODE,
+};
+use futures::Stream;
+pub use multi_factor::*;
+pub use phone::*;
+pub use provider::*;
 use std::{error::Error, fmt};
 pub use user::*;
 use wasm_bindgen::{prelude::*, JsCast};
@@ -24,15 +33,24 @@ impl Error for AuthError {
     }
 }
 
-impl From<FirebaseError> for AuthError {
-    fn from(err: FirebaseError) -> Self {
+impl From<serde_wasm_bindgen::Error> for AuthError {
+    fn from(err: serde_wasm_bindgen::Error) -> Self {
         Self {
-            kind: err.code().parse().unwrap(),
-            source: err,
+            kind: AuthErrorKind::Serialization,
+            source: FirebaseError::from_serde_error(err, SERIALIZATION_ERROR_CODE),
         }
     }
 }
 
+impl From<FirebaseError> for AuthError {
+    fn from(err: FirebaseError) -> Self {
+        let code = err.code();
+        let kind = code.parse().unwrap_or_else(|_| AuthErrorKind::Other(code));
+
+        Self { kind, source: err }
+    }
+}
+
 #[derive(Clone, Debug, strum_macros::EnumString)]
 #[non_exhaustive]
 pub enum AuthErrorKind {
@@ -86,16 +104,118 @@ pub enum AuthErrorKind {
     UnauthorizedContinueUri,
     #[strum(serialize = "auth/expired-action-code")]
     ExpiredActionCode,
+    #[strum(serialize = "auth/invalid-custom-token")]
+    InvalidCustomToken,
+    #[strum(serialize = "auth/custom-token-mismatch")]
+    CustomTokenMismatch,
+    #[strum(serialize = "auth/popup-closed-by-user")]
+    PopupClosedByUser,
+    #[strum(serialize = "auth/popup-blocked")]
+    PopupBlocked,
+    #[strum(serialize = "auth/account-exists-with-different-credential")]
+    AccountExistsWithDifferentCredential,
+    #[strum(serialize = "auth/invalid-phone-number")]
+    InvalidPhoneNumber,
+    #[strum(serialize = "auth/invalid-verification-code")]
+    InvalidVerificationCode,
+    #[strum(serialize = "auth/code-expired")]
+    CodeExpired,
+    #[strum(serialize = "auth/multi-factor-auth-required")]
+    MultiFactorAuthRequired,
+    #[strum(serialize = "auth/invalid-action-code")]
+    InvalidActionCode,
+    #[strum(serialize = "auth/invalid-new-email")]
+    InvalidNewEmail,
+    #[strum(serialize = "auth/credential-already-in-use")]
+    CredentialAlreadyInUse,
+    #[strum(serialize = "auth/provider-already-linked")]
+    ProviderAlreadyLinked,
+    #[strum(serialize = "auth/invalid-credential")]
+    InvalidCredential,
+    #[strum(serialize = "auth/cancelled-popup-request")]
+    CancelledPopupRequest,
+    #[strum(serialize = "auth/invalid-verification-id")]
+    InvalidVerificationId,
+    #[strum(serialize = "auth/missing-verification-code")]
+    MissingVerificationCode,
+    #[strum(serialize = "auth/no-such-provider")]
+    NoSuchProvider,
+    /// Arguments could not be converted to or from JS values.
+    #[strum(serialize = "firebase-wasm/serialization")]
+    Serialization,
     #[strum(default)]
     Other(String),
 }
 
+/// User-facing messages, suitable for showing in a UI.
+impl fmt::Display for AuthErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            Self::AppDeleted => "This app is no longer available.",
+            Self::AppNotAuthorized => "This app is not authorized to sign in.",
+            Self::ArgumentError => "The request was invalid.",
+            Self::InvalidApiKey => "This app is misconfigured.",
+            Self::InvalidUserToken => "Your session is no longer valid. Please sign in again.",
+            Self::InvalidTenantId => "The tenant is invalid.",
+            Self::NetworkRequestFailed => {
+                "A network error occurred. Please check your connection and try again."
+            }
+            Self::OperationNotAllowed => "This sign-in method is not enabled.",
+            Self::RequiresRecentLogin => "Please sign in again to continue.",
+            Self::TooManyRequests => "Too many attempts. Please try again later.",
+            Self::UnauthorizedDomain => "Sign-in is not allowed from this domain.",
+            Self::UserDisabled => "This account has been disabled.",
+            Self::UserTokenExpired => "Your session has expired. Please sign in again.",
+            Self::WebStorageUnsupported => "Your browser does not support web storage.",
+            Self::InvalidEmail => "The email address is invalid.",
+            Self::UserNotFound => "There is no account with this email address.",
+            Self::WrongPassword => "The password is incorrect.",
+            Self::EmailAlreadyInUse => "An account with this email address already exists.",
+            Self::WeakPassword => "The password is too weak.",
+            Self::MissingAndroidPackageName => "An Android package name is required.",
+            Self::MissingContinueUri => "A continue URL is required.",
+            Self::MissingIOSBundleId => "An iOS bundle ID is required.",
+            Self::InvalidContinueUri => "The continue URL is invalid.",
+            Self::UnauthorizedContinueUri => "The continue URL's domain is not allowed.",
+            Self::ExpiredActionCode => "This link has expired.",
+            Self::InvalidCustomToken => "The sign-in token is invalid.",
+            Self::CustomTokenMismatch => "The sign-in token is for a different project.",
+            Self::PopupClosedByUser => "The sign-in popup was closed before finishing.",
+            Self::PopupBlocked => "The sign-in popup was blocked by the browser.",
+            Self::AccountExistsWithDifferentCredential => {
+                "An account already exists with this email address using a different \
+                 sign-in method."
+            }
+            Self::InvalidPhoneNumber => "The phone number is invalid.",
+            Self::InvalidVerificationCode => "The verification code is incorrect.",
+            Self::CodeExpired => "The verification code has expired.",
+            Self::MultiFactorAuthRequired => "Additional verification is required to sign in.",
+            Self::InvalidActionCode => "This link is invalid or has already been used.",
+            Self::InvalidNewEmail => "The new email address is invalid.",
+            Self::CredentialAlreadyInUse => "These credentials are linked to another account.",
+            Self::ProviderAlreadyLinked => "This sign-in method is already linked.",
+            Self::InvalidCredential => "The credentials are invalid or have expired.",
+            Self::CancelledPopupRequest => "Another sign-in popup is already open.",
+            Self::InvalidVerificationId => "The verification request is invalid.",
+            Self::MissingVerificationCode => "A verification code is required.",
+            Self::NoSuchProvider => "This sign-in method is not linked.",
+            Self::Serialization => "An unexpected error occurred.",
+            Self::Other(code) => code,
+        };
+
+        f.write_str(str)
+    }
+}
+
+#[serde_with::skip_serializing_none]
 #[derive(Debug, Clone, TypedBuilder, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 #[builder(field_defaults(default))]
 pub struct ActionCodeSettings {
     pub android: Option<AndroidActionCodeSettings>,
     #[builder(setter(strip_option))]
     pub handle_code_in_app: Option<bool>,
+    #[serde(rename = "iOS")]
     pub ios: Option<IOSActionCodeSettings>,
     #[builder(!default)]
     pub url: String,
@@ -120,6 +240,133 @@ pub struct IOSActionCodeSettings {
     pub bundle_id: String,
 }
 
+/// Fields left as `None` are left untouched on the user's profile.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Default, PartialEq, Eq, TypedBuilder, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(strip_option, into)))]
+pub struct ProfileUpdate {
+    pub display_name: Option<String>,
+    #[serde(rename = "photoURL")]
+    pub photo_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct ActionCodeInfo {
+    pub data: ActionCodeInfoData,
+    pub operation: ActionCodeOperation,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionCodeInfoData {
+    pub email: Option<String>,
+    pub previous_email: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ActionCodeOperation {
+    #[serde(rename = "EMAIL_SIGNIN")]
+    EmailSignIn,
+    PasswordReset,
+    RecoverEmail,
+    RevertSecondFactorAddition,
+    VerifyAndChangeEmail,
+    VerifyEmail,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Persistence {
+    /// `browserLocalPersistence`
+    BrowserLocal,
+    /// `browserSessionPersistence`
+    BrowserSession,
+    /// `indexedDBLocalPersistence`
+    IndexedDbLocal,
+    /// `inMemoryPersistence`
+    InMemory,
+}
+
+/// Connects to the Auth emulator at `url`, such as
+/// `"http://127.0.0.1:9099"`.
+///
+/// Must be called before any other auth operation, otherwise it fails.
+pub fn connect_auth_emulator(
+    auth: Auth,
+    url: &str,
+    disable_warnings: bool,
+) -> Result<(), AuthError> {
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &options,
+        &"disableWarnings".into(),
+        &disable_warnings.into(),
+    )
+    .unwrap();
+
+    connect_auth_emulator_js(auth, url, options.into()).map_err(Into::into)
+}
+
+/// Sets `user` as the current user of `auth`, or signs out when `None`.
+pub async fn update_current_user(auth: Auth, user: Option<&User>) -> Result<(), AuthError> {
+    update_current_user_js(auth, user)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Must be awaited before signing in for the persistence to take effect.
+pub async fn set_persistence(auth: Auth, persistence: Persistence) -> Result<(), AuthError> {
+    let persistence = match persistence {
+        Persistence::BrowserLocal => BROWSER_LOCAL_PERSISTENCE.clone(),
+        Persistence::BrowserSession => BROWSER_SESSION_PERSISTENCE.clone(),
+        Persistence::IndexedDbLocal => INDEXED_DB_LOCAL_PERSISTENCE.clone(),
+        Persistence::InMemory => IN_MEMORY_PERSISTENCE.clone(),
+    };
+
+    set_persistence_js(auth, persistence)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub fn on_auth_state_changed(
+    auth: Auth,
+    callback: Closure<dyn FnMut(Option<User>)>,
+) -> Unsubscribe {
+    let unsubscribe = on_auth_state_changed_js(auth, &callback);
+
+    Unsubscribe::new(unsubscribe, callback)
+}
+
+/// Unlike [`on_auth_state_changed`], this also fires whenever the ID
+/// token is refreshed.
+pub fn on_id_token_changed(auth: Auth, callback: Closure<dyn FnMut(Option<User>)>) -> Unsubscribe {
+    let unsubscribe = on_id_token_changed_js(auth, &callback);
+
+    Unsubscribe::new(unsubscribe, callback)
+}
+
+/// Runs `callback` before each auth state change is committed. If the
+/// returned promise rejects, the state change is blocked and `on_abort` is
+/// called.
+pub fn before_auth_state_changed(
+    auth: Auth,
+    callback: Closure<dyn FnMut(Option<User>) -> js_sys::Promise>,
+    on_abort: Option<Closure<dyn FnMut()>>,
+) -> Unsubscribe {
+    let unsubscribe = before_auth_state_changed_js(auth, &callback, on_abort.as_ref());
+
+    Unsubscribe::new(unsubscribe, (callback, on_abort))
+}
+
+/// Stream of auth state changes, which stops listening when dropped.
+pub fn auth_state_stream(auth: Auth) -> impl Stream<Item = Option<User>> {
+    callback_stream(
+        |callback| on_auth_state_changed_js(auth, callback),
+        |user| user,
+    )
+}
+
 pub async fn create_user_with_email_and_password(
     auth: Auth,
     email: String,
@@ -142,12 +389,60 @@ pub async fn sign_in_with_email_and_password(
         .map_err(|err| err.unchecked_into::<FirebaseError>().into())
 }
 
+pub async fn sign_in_anonymously(auth: Auth) -> Result<UserCredential, AuthError> {
+    sign_in_anonymously_js(auth)
+        .await
+        .map(|cred| cred.unchecked_into::<UserCredential>())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub async fn sign_in_with_custom_token(
+    auth: Auth,
+    token: &str,
+) -> Result<UserCredential, AuthError> {
+    sign_in_with_custom_token_js(auth, token)
+        .await
+        .map(|cred| cred.unchecked_into::<UserCredential>())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub async fn sign_in_with_popup(
+    auth: Auth,
+    provider: &AuthProvider,
+) -> Result<UserCredential, AuthError> {
+    sign_in_with_popup_js(auth, provider)
+        .await
+        .map(|cred| cred.unchecked_into::<UserCredential>())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub async fn sign_in_with_redirect(auth: Auth, provider: &AuthProvider) -> Result<(), AuthError> {
+    sign_in_with_redirect_js(auth, provider)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Returns `None` if no redirect sign-in is pending, such as on a fresh
+/// page load.
+pub async fn get_redirect_result(auth: Auth) -> Result<Option<UserCredential>, AuthError> {
+    get_redirect_result_js(auth)
+        .await
+        .map(|cred| {
+            if cred.is_null() || cred.is_undefined() {
+                None
+            } else {
+                Some(cred.unchecked_into::<UserCredential>())
+            }
+        })
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
 pub async fn send_sign_in_link_to_email(
     auth: Auth,
     email: String,
     action_code_settings: ActionCodeSettings,
 ) -> Result<(), AuthError> {
-    let action_code_settings = serde_wasm_bindgen::to_value(&action_code_settings).unwrap();
+    let action_code_settings = serde_wasm_bindgen::to_value(&action_code_settings)?;
 
     send_sign_in_link_to_email_js(auth, email, action_code_settings)
         .await
@@ -168,17 +463,21 @@ pub async fn sign_in_with_email_link(
 pub async fn send_password_reset_email(
     auth: Auth,
     email: String,
-    action_code_settings: Option<ActionCodeSettings>,
+    action_code_settings: Option<&ActionCodeSettings>,
 ) -> Result<(), AuthError> {
-    let action_code_settings = serde_wasm_bindgen::to_value(&action_code_settings).unwrap();
+    let action_code_settings = action_code_settings
+        .map(serde_wasm_bindgen::to_value)
+        .transpose()?
+        .unwrap_or(JsValue::UNDEFINED);
 
     send_password_reset_email_js(auth, email, action_code_settings)
         .await
         .map_err(|err| err.unchecked_into::<FirebaseError>().into())
 }
 
-pub async fn verify_password_reset_code(auth: Auth, code: String) -> Result<String, AuthError> {
-    verify_password_reset_code_js(auth, code)
+/// Returns the email address the password reset code was issued for.
+pub async fn verify_password_reset_code(auth: Auth, oob_code: &str) -> Result<String, AuthError> {
+    verify_password_reset_code_js(auth, oob_code)
         .await
         .map(|res| res.unchecked_into::<js_sys::JsString>())
         .map(|s| ToString::to_string(&s))
@@ -187,11 +486,199 @@ pub async fn verify_password_reset_code(auth: Auth, code: String) -> Result<Stri
 
 pub async fn confirm_password_reset(
     auth: Auth,
-    code: String,
-    new_password: String,
-) -> Result<(), JsValue> {
-    confirm_password_reset_js(auth, code, new_password)
+    oob_code: &str,
+    new_password: &str,
+) -> Result<(), AuthError> {
+    confirm_password_reset_js(auth, oob_code, new_password)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub async fn apply_action_code(auth: Auth, oob_code: &str) -> Result<(), AuthError> {
+    apply_action_code_js(auth, oob_code)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub async fn check_action_code(auth: Auth, oob_code: &str) -> Result<ActionCodeInfo, AuthError> {
+    let info = check_action_code_js(auth, oob_code)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>())?;
+
+    Ok(serde_wasm_bindgen::from_value::<ActionCodeInfo>(info)?)
+}
+
+/// Updates the user's profile in place, so the [`User`] getters reflect
+/// the new values without needing a reload.
+pub async fn update_profile(user: &User, profile: &ProfileUpdate) -> Result<(), AuthError> {
+    let profile = serde_wasm_bindgen::to_value(profile)?;
+
+    update_profile_js(user, profile)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Newer SDKs require the new address to be verified first, so prefer
+/// [`verify_before_update_email`] where possible.
+///
+/// Fails with [`AuthErrorKind::RequiresRecentLogin`] if the user has not
+/// signed in recently, in which case they must be reauthenticated.
+pub async fn update_email(user: &User, new_email: &str) -> Result<(), AuthError> {
+    update_email_js(user, new_email)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Fails with [`AuthErrorKind::RequiresRecentLogin`] if the user has not
+/// signed in recently, in which case they must be reauthenticated.
+pub async fn update_password(user: &User, new_password: &str) -> Result<(), AuthError> {
+    update_password_js(user, new_password)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Sends a verification link to `new_email`, and only updates the user's
+/// email once it has been followed. This is the recommended replacement for
+/// [`update_email`].
+pub async fn verify_before_update_email(
+    user: &User,
+    new_email: &str,
+    action_code_settings: Option<&ActionCodeSettings>,
+) -> Result<(), AuthError> {
+    let action_code_settings = action_code_settings
+        .map(serde_wasm_bindgen::to_value)
+        .transpose()?
+        .unwrap_or(JsValue::UNDEFINED);
+
+    verify_before_update_email_js(user, new_email, action_code_settings)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub async fn reauthenticate_with_credential(
+    user: &User,
+    credential: &AuthCredential,
+) -> Result<UserCredential, AuthError> {
+    reauthenticate_with_credential_js(user, credential)
+        .await
+        .map(|cred| cred.unchecked_into::<UserCredential>())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub async fn reauthenticate_with_popup(
+    user: &User,
+    provider: &AuthProvider,
+) -> Result<UserCredential, AuthError> {
+    reauthenticate_with_popup_js(user, provider)
+        .await
+        .map(|cred| cred.unchecked_into::<UserCredential>())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Refreshes the user's cached profile data, such as after their email was
+/// verified in another tab.
+///
+/// ```no_run
+/// # use firebase_wasm::auth::{reload, AuthError, User};
+/// # async fn example(user: User) -> Result<(), AuthError> {
+/// reload(&user).await?;
+///
+/// if user.email_verified() {
+///     // ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn reload(user: &User) -> Result<(), AuthError> {
+    reload_js(user)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Returns the raw JWT, suitable for use as an `Authorization: Bearer`
+/// token with backend APIs.
+pub async fn get_id_token(user: &User, force_refresh: bool) -> Result<String, AuthError> {
+    get_id_token_js(user, force_refresh)
+        .await
+        .map(|token| token.unchecked_into::<js_sys::JsString>())
+        .map(|token| ToString::to_string(&token))
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Custom claims can be read with [`IdTokenResult::claims_map`], or with
+/// [`ParsedToken::custom_claims`] on [`IdTokenResult::claims`].
+pub async fn get_id_token_result(
+    user: &User,
+    force_refresh: bool,
+) -> Result<IdTokenResult, AuthError> {
+    get_id_token_result_js(user, force_refresh)
+        .await
+        .map(|res| res.unchecked_into::<IdTokenResult>())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Links the user with the given credential, such as when upgrading an
+/// anonymous account to a permanent one.
+pub async fn link_with_credential(
+    user: &User,
+    credential: &AuthCredential,
+) -> Result<UserCredential, AuthError> {
+    link_with_credential_js(user, credential)
+        .await
+        .map(|cred| cred.unchecked_into::<UserCredential>())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub async fn link_with_popup(
+    user: &User,
+    provider: &AuthProvider,
+) -> Result<UserCredential, AuthError> {
+    link_with_popup_js(user, provider)
+        .await
+        .map(|cred| cred.unchecked_into::<UserCredential>())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Unlinks the provider from the user, returning the updated user.
+pub async fn unlink(user: &User, provider_id: &str) -> Result<User, AuthError> {
+    unlink_js(user, provider_id)
+        .await
+        .map(|user| user.unchecked_into::<User>())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Deletes and signs out the user.
+///
+/// Fails with [`AuthErrorKind::RequiresRecentLogin`] if the user has not
+/// signed in recently, in which case they must be reauthenticated.
+pub async fn delete_user(user: &User) -> Result<(), AuthError> {
+    delete_user_js(user)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub async fn send_email_verification(
+    user: &User,
+    action_code_settings: Option<&ActionCodeSettings>,
+) -> Result<(), AuthError> {
+    let action_code_settings = action_code_settings
+        .map(serde_wasm_bindgen::to_value)
+        .transpose()?
+        .unwrap_or(JsValue::UNDEFINED);
+
+    send_email_verification_js(user, action_code_settings)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Checks `password` against the project's password policy.
+pub async fn validate_password(
+    auth: Auth,
+    password: &str,
+) -> Result<PasswordValidationStatus, AuthError> {
+    validate_password_js(auth, password)
         .await
+        .map(|status| status.unchecked_into::<PasswordValidationStatus>())
         .map_err(|err| err.unchecked_into::<FirebaseError>().into())
 }
 
@@ -204,16 +691,107 @@ pub struct UserCredential {
     pub operation_type: String,
 }
 
+/// Requirements which are not part of the project's password policy are
+/// `None`.
+#[wasm_bindgen_struct]
+#[opts(module = "firebase/auth", getter)]
+#[derive(Clone, Debug)]
+pub struct PasswordValidationStatus {
+    pub is_valid: bool,
+    pub meets_min_password_length: Option<bool>,
+    pub meets_max_password_length: Option<bool>,
+    pub contains_lowercase_letter: Option<bool>,
+    pub contains_uppercase_letter: Option<bool>,
+    pub contains_numeric_character: Option<bool>,
+    pub contains_non_alphanumeric_character: Option<bool>,
+}
+
+#[wasm_bindgen_struct]
+#[opts(module = "firebase/auth", getter)]
+#[derive(Clone, Debug)]
+pub struct AdditionalUserInfo {
+    pub is_new_user: bool,
+    pub provider_id: Option<String>,
+    pub username: Option<String>,
+    pub profile: JsValue,
+}
+
 #[wasm_bindgen(module = "firebase/auth")]
 extern "C" {
     #[derive(Clone, Debug)]
     pub type Auth;
 
+    /// Returns the [`Auth`] instance of `app`, or of the default app when
+    /// `None`.
     #[wasm_bindgen(js_name = getAuth)]
-    pub fn get_auth() -> Auth;
+    pub fn get_auth(app: Option<&FirebaseApp>) -> Auth;
+
+    /// Returns `None` when signed out.
+    #[wasm_bindgen(method, getter, js_name = currentUser)]
+    pub fn current_user(this: &Auth) -> Option<User>;
+
+    #[wasm_bindgen(method, getter, js_name = languageCode)]
+    pub fn language_code(this: &Auth) -> Option<String>;
+
+    /// Sets the locale used for verification and password reset emails,
+    /// or resets it to the project default when `None`.
+    #[wasm_bindgen(method, setter, js_name = languageCode)]
+    pub fn set_language_code(this: &Auth, language_code: Option<&str>);
+
+    #[wasm_bindgen(method, getter, js_name = tenantId)]
+    pub fn tenant_id(this: &Auth) -> Option<String>;
+
+    /// Scopes all subsequent sign-in calls to the given tenant, or to the
+    /// parent project when `None`.
+    #[wasm_bindgen(method, setter, js_name = tenantId)]
+    pub fn set_tenant_id(this: &Auth, tenant_id: Option<&str>);
+
+    #[wasm_bindgen(js_name = useDeviceLanguage)]
+    pub fn use_device_language(auth: Auth);
+
+    #[wasm_bindgen(js_name = connectAuthEmulator, catch)]
+    fn connect_auth_emulator_js(
+        auth: Auth,
+        url: &str,
+        options: JsValue,
+    ) -> Result<(), FirebaseError>;
+
+    #[wasm_bindgen(js_name = browserLocalPersistence)]
+    static BROWSER_LOCAL_PERSISTENCE: JsValue;
+
+    #[wasm_bindgen(js_name = browserSessionPersistence)]
+    static BROWSER_SESSION_PERSISTENCE: JsValue;
+
+    #[wasm_bindgen(js_name = indexedDBLocalPersistence)]
+    static INDEXED_DB_LOCAL_PERSISTENCE: JsValue;
+
+    #[wasm_bindgen(js_name = inMemoryPersistence)]
+    static IN_MEMORY_PERSISTENCE: JsValue;
+
+    #[wasm_bindgen(js_name = updateCurrentUser, catch)]
+    async fn update_current_user_js(auth: Auth, user: Option<&User>) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = setPersistence, catch)]
+    async fn set_persistence_js(auth: Auth, persistence: JsValue) -> Result<(), JsValue>;
 
     #[wasm_bindgen(js_name = onAuthStateChanged)]
-    pub fn on_auth_state_changed(auth: Auth, callback: &Closure<dyn FnMut(Option<User>)>);
+    fn on_auth_state_changed_js(
+        auth: Auth,
+        callback: &Closure<dyn FnMut(Option<User>)>,
+    ) -> js_sys::Function;
+
+    #[wasm_bindgen(js_name = beforeAuthStateChanged)]
+    fn before_auth_state_changed_js(
+        auth: Auth,
+        callback: &Closure<dyn FnMut(Option<User>) -> js_sys::Promise>,
+        on_abort: Option<&Closure<dyn FnMut()>>,
+    ) -> js_sys::Function;
+
+    #[wasm_bindgen(js_name = onIdTokenChanged)]
+    fn on_id_token_changed_js(
+        auth: Auth,
+        callback: &Closure<dyn FnMut(Option<User>)>,
+    ) -> js_sys::Function;
 
     #[wasm_bindgen(js_name = createUserWithEmailAndPassword, catch)]
     async fn create_user_with_email_and_password_js(
@@ -229,6 +807,22 @@ extern "C" {
         password: String,
     ) -> Result<JsValue, JsValue>;
 
+    #[wasm_bindgen(js_name = signInAnonymously, catch)]
+    async fn sign_in_anonymously_js(auth: Auth) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = signInWithCustomToken, catch)]
+    async fn sign_in_with_custom_token_js(auth: Auth, token: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = signInWithPopup, catch)]
+    async fn sign_in_with_popup_js(auth: Auth, provider: &AuthProvider)
+        -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = signInWithRedirect, catch)]
+    async fn sign_in_with_redirect_js(auth: Auth, provider: &AuthProvider) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = getRedirectResult, catch)]
+    async fn get_redirect_result_js(auth: Auth) -> Result<JsValue, JsValue>;
+
     #[wasm_bindgen(js_name = signInWithEmailLink, catch)]
     async fn sign_in_with_email_link_js(
         auth: Auth,
@@ -236,6 +830,12 @@ extern "C" {
         email_link: String,
     ) -> Result<JsValue, JsValue>;
 
+    #[wasm_bindgen(js_name = validatePassword, catch)]
+    async fn validate_password_js(auth: Auth, password: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getAdditionalUserInfo)]
+    pub fn get_additional_user_info(credential: &UserCredential) -> Option<AdditionalUserInfo>;
+
     #[wasm_bindgen(js_name = isSignInWithEmailLink, )]
     pub fn is_sign_in_with_email_link(auth: Auth, email_link: &str) -> bool;
 
@@ -256,13 +856,77 @@ extern "C" {
         action_code_settings: JsValue,
     ) -> Result<(), JsValue>;
 
+    #[wasm_bindgen(js_name = applyActionCode, catch)]
+    async fn apply_action_code_js(auth: Auth, oob_code: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = checkActionCode, catch)]
+    async fn check_action_code_js(auth: Auth, oob_code: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = updateProfile, catch)]
+    async fn update_profile_js(user: &User, profile: JsValue) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = updateEmail, catch)]
+    async fn update_email_js(user: &User, new_email: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = updatePassword, catch)]
+    async fn update_password_js(user: &User, new_password: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = verifyBeforeUpdateEmail, catch)]
+    async fn verify_before_update_email_js(
+        user: &User,
+        new_email: &str,
+        action_code_settings: JsValue,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = reauthenticateWithCredential, catch)]
+    async fn reauthenticate_with_credential_js(
+        user: &User,
+        credential: &AuthCredential,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = reauthenticateWithPopup, catch)]
+    async fn reauthenticate_with_popup_js(
+        user: &User,
+        provider: &AuthProvider,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = reload, catch)]
+    async fn reload_js(user: &User) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = getIdToken, catch)]
+    async fn get_id_token_js(user: &User, force_refresh: bool) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getIdTokenResult, catch)]
+    async fn get_id_token_result_js(user: &User, force_refresh: bool) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = linkWithCredential, catch)]
+    async fn link_with_credential_js(
+        user: &User,
+        credential: &AuthCredential,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = linkWithPopup, catch)]
+    async fn link_with_popup_js(user: &User, provider: &AuthProvider) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = unlink, catch)]
+    async fn unlink_js(user: &User, provider_id: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = deleteUser, catch)]
+    async fn delete_user_js(user: &User) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = sendEmailVerification, catch)]
+    async fn send_email_verification_js(
+        user: &User,
+        action_code_settings: JsValue,
+    ) -> Result<(), JsValue>;
+
     #[wasm_bindgen(js_name = verifyPasswordResetCode, catch)]
-    async fn verify_password_reset_code_js(auth: Auth, code: String) -> Result<JsValue, JsValue>;
+    async fn verify_password_reset_code_js(auth: Auth, oob_code: &str) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(js_name = confirmPasswordReset, catch)]
     async fn confirm_password_reset_js(
         auth: Auth,
-        code: String,
-        new_password: String,
+        oob_code: &str,
+        new_password: &str,
     ) -> Result<(), JsValue>;
 }
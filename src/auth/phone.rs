@@ -0,0 +1,77 @@
+use super::{Auth, AuthError, UserCredential};
+use crate::FirebaseError;
+use wasm_bindgen::{prelude::*, JsCast};
+
+pub async fn sign_in_with_phone_number(
+    auth: Auth,
+    phone_number: &str,
+    app_verifier: &RecaptchaVerifier,
+) -> Result<ConfirmationResult, AuthError> {
+    sign_in_with_phone_number_js(auth, phone_number, app_verifier)
+        .await
+        .map(|res| res.unchecked_into::<ConfirmationResult>())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+impl RecaptchaVerifier {
+    /// Renders the reCAPTCHA widget, returning its widget ID.
+    pub async fn render(&self) -> Result<i32, AuthError> {
+        let id = self
+            .render_js()
+            .await
+            .map_err(|err| err.unchecked_into::<FirebaseError>())?;
+
+        Ok(serde_wasm_bindgen::from_value(id)?)
+    }
+}
+
+impl ConfirmationResult {
+    pub async fn confirm(&self, verification_code: &str) -> Result<UserCredential, AuthError> {
+        self.confirm_js(verification_code)
+            .await
+            .map(|cred| cred.unchecked_into::<UserCredential>())
+            .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+    }
+}
+
+#[wasm_bindgen(module = "firebase/auth")]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type RecaptchaVerifier;
+
+    #[derive(Clone, Debug)]
+    pub type ConfirmationResult;
+
+    #[wasm_bindgen(js_name = signInWithPhoneNumber, catch)]
+    async fn sign_in_with_phone_number_js(
+        auth: Auth,
+        phone_number: &str,
+        app_verifier: &RecaptchaVerifier,
+    ) -> Result<JsValue, JsValue>;
+
+    // =========================================================================
+    //                            RecaptchaVerifier
+    // =========================================================================
+
+    #[wasm_bindgen(constructor)]
+    pub fn new(auth: Auth, container_id: &str, parameters: JsValue) -> RecaptchaVerifier;
+
+    #[wasm_bindgen(method, js_name = render, catch)]
+    async fn render_js(this: &RecaptchaVerifier) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method)]
+    pub fn clear(this: &RecaptchaVerifier);
+
+    // =========================================================================
+    //                            ConfirmationResult
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter, js_name = verificationId)]
+    pub fn verification_id(this: &ConfirmationResult) -> String;
+
+    #[wasm_bindgen(method, js_name = confirm, catch)]
+    async fn confirm_js(
+        this: &ConfirmationResult,
+        verification_code: &str,
+    ) -> Result<JsValue, JsValue>;
+}
@@ -0,0 +1,213 @@
+use super::UserCredential;
+use crate::FirebaseError;
+use wasm_bindgen::prelude::*;
+
+impl BaseOAuthProvider {
+    pub fn get_scopes(&self) -> Vec<String> {
+        self.get_scopes_js()
+            .iter()
+            .filter_map(|scope| scope.as_string())
+            .collect()
+    }
+}
+
+#[wasm_bindgen(module = "firebase/auth")]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type AuthProvider;
+
+    #[derive(Clone, Debug)]
+    pub type AuthCredential;
+
+    #[wasm_bindgen(extends = AuthCredential)]
+    #[derive(Clone, Debug)]
+    pub type OAuthCredential;
+
+    #[derive(Clone, Debug)]
+    pub type EmailAuthProvider;
+
+    /// The base of the OAuth providers, such as [`GoogleAuthProvider`],
+    /// which they deref to.
+    #[wasm_bindgen(extends = AuthProvider)]
+    #[derive(Clone, Debug)]
+    pub type BaseOAuthProvider;
+
+    #[wasm_bindgen(extends = BaseOAuthProvider, extends = AuthProvider)]
+    #[derive(Clone, Debug)]
+    pub type OAuthProvider;
+
+    #[wasm_bindgen(extends = BaseOAuthProvider, extends = AuthProvider)]
+    #[derive(Clone, Debug)]
+    pub type GoogleAuthProvider;
+
+    #[wasm_bindgen(extends = BaseOAuthProvider, extends = AuthProvider)]
+    #[derive(Clone, Debug)]
+    pub type GithubAuthProvider;
+
+    #[wasm_bindgen(extends = BaseOAuthProvider, extends = AuthProvider)]
+    #[derive(Clone, Debug)]
+    pub type FacebookAuthProvider;
+
+    // =========================================================================
+    //                              AuthProvider
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter, js_name = providerId)]
+    pub fn provider_id(this: &AuthProvider) -> String;
+
+    // =========================================================================
+    //                             AuthCredential
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter, js_name = providerId)]
+    pub fn provider_id(this: &AuthCredential) -> String;
+
+    #[wasm_bindgen(method, getter, js_name = signInMethod)]
+    pub fn sign_in_method(this: &AuthCredential) -> String;
+
+    #[wasm_bindgen(method, js_name = toJSON)]
+    pub fn to_json(this: &AuthCredential) -> js_sys::Object;
+
+    // =========================================================================
+    //                            OAuthCredential
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter, js_name = accessToken)]
+    pub fn access_token(this: &OAuthCredential) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = idToken)]
+    pub fn id_token(this: &OAuthCredential) -> Option<String>;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn secret(this: &OAuthCredential) -> Option<String>;
+
+    // =========================================================================
+    //                            EmailAuthProvider
+    // =========================================================================
+
+    #[wasm_bindgen(static_method_of = EmailAuthProvider)]
+    pub fn credential(email: &str, password: &str) -> AuthCredential;
+
+    // =========================================================================
+    //                            BaseOAuthProvider
+    // =========================================================================
+
+    #[wasm_bindgen(method, js_name = getScopes)]
+    fn get_scopes_js(this: &BaseOAuthProvider) -> js_sys::Array;
+
+    // =========================================================================
+    //                              OAuthProvider
+    // =========================================================================
+
+    /// Creates a provider for a generic OAuth vendor, such as
+    /// `"microsoft.com"`, `"apple.com"` or `"yahoo.com"`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(provider_id: &str) -> OAuthProvider;
+
+    #[wasm_bindgen(method, js_name = addScope)]
+    pub fn add_scope(this: &OAuthProvider, scope: &str) -> OAuthProvider;
+
+    #[wasm_bindgen(method, js_name = setCustomParameters)]
+    pub fn set_custom_parameters(
+        this: &OAuthProvider,
+        custom_oauth_parameters: JsValue,
+    ) -> OAuthProvider;
+
+    #[wasm_bindgen(method)]
+    pub fn credential(this: &OAuthProvider, params: JsValue) -> OAuthCredential;
+
+    #[wasm_bindgen(static_method_of = OAuthProvider, js_name = credentialFromResult)]
+    pub fn credential_from_result(user_credential: &UserCredential) -> Option<OAuthCredential>;
+
+    #[wasm_bindgen(static_method_of = OAuthProvider, js_name = credentialFromError)]
+    pub fn credential_from_error(error: &FirebaseError) -> Option<OAuthCredential>;
+
+    // =========================================================================
+    //                            GoogleAuthProvider
+    // =========================================================================
+
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> GoogleAuthProvider;
+
+    #[wasm_bindgen(method, js_name = addScope)]
+    pub fn add_scope(this: &GoogleAuthProvider, scope: &str) -> GoogleAuthProvider;
+
+    #[wasm_bindgen(method, js_name = setCustomParameters)]
+    pub fn set_custom_parameters(
+        this: &GoogleAuthProvider,
+        custom_oauth_parameters: JsValue,
+    ) -> GoogleAuthProvider;
+
+    #[wasm_bindgen(static_method_of = GoogleAuthProvider, js_name = credentialFromResult)]
+    pub fn credential_from_result(user_credential: &UserCredential) -> Option<OAuthCredential>;
+
+    // =========================================================================
+    //                            GithubAuthProvider
+    // =========================================================================
+
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> GithubAuthProvider;
+
+    #[wasm_bindgen(method, js_name = addScope)]
+    pub fn add_scope(this: &GithubAuthProvider, scope: &str) -> GithubAuthProvider;
+
+    #[wasm_bindgen(method, js_name = setCustomParameters)]
+    pub fn set_custom_parameters(
+        this: &GithubAuthProvider,
+        custom_oauth_parameters: JsValue,
+    ) -> GithubAuthProvider;
+
+    #[wasm_bindgen(static_method_of = GithubAuthProvider)]
+    pub fn credential(access_token: &str) -> OAuthCredential;
+
+    #[wasm_bindgen(static_method_of = GithubAuthProvider, js_name = credentialFromResult)]
+    pub fn credential_from_result(user_credential: &UserCredential) -> Option<OAuthCredential>;
+
+    /// Recovers the pending credential from an
+    /// [`AccountExistsWithDifferentCredential`](super::AuthErrorKind::AccountExistsWithDifferentCredential)
+    /// error, so it can be linked once the user signs in with their
+    /// existing provider.
+    #[wasm_bindgen(static_method_of = GithubAuthProvider, js_name = credentialFromError)]
+    pub fn credential_from_error(error: &FirebaseError) -> Option<OAuthCredential>;
+
+    // =========================================================================
+    //                           FacebookAuthProvider
+    // =========================================================================
+
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> FacebookAuthProvider;
+
+    #[wasm_bindgen(method, js_name = addScope)]
+    pub fn add_scope(this: &FacebookAuthProvider, scope: &str) -> FacebookAuthProvider;
+
+    #[wasm_bindgen(method, js_name = setCustomParameters)]
+    pub fn set_custom_parameters(
+        this: &FacebookAuthProvider,
+        custom_oauth_parameters: JsValue,
+    ) -> FacebookAuthProvider;
+
+    #[wasm_bindgen(static_method_of = FacebookAuthProvider)]
+    pub fn credential(access_token: &str) -> OAuthCredential;
+
+    #[wasm_bindgen(static_method_of = FacebookAuthProvider, js_name = credentialFromResult)]
+    pub fn credential_from_result(user_credential: &UserCredential) -> Option<OAuthCredential>;
+
+    #[wasm_bindgen(static_method_of = FacebookAuthProvider, js_name = credentialFromError)]
+    pub fn credential_from_error(error: &FirebaseError) -> Option<OAuthCredential>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn add_scope_keeps_earlier_scopes() {
+        let provider = FacebookAuthProvider::new();
+
+        provider.add_scope("email");
+        provider.add_scope("user_birthday");
+
+        assert_eq!(provider.get_scopes(), ["email", "user_birthday"]);
+    }
+}
@@ -1,6 +1,7 @@
 use super::AuthError;
 use crate::FirebaseError;
 use serde::Deserialize;
+use std::collections::HashMap;
 use wasm_bindgen::{prelude::*, JsCast};
 
 impl ParsedToken {
@@ -25,12 +26,28 @@ pub struct UserInfo {
     pub uid: String,
 }
 
+#[cfg(feature = "chrono")]
+impl UserMetadata {
+    pub fn creation_date_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.creation_time()
+            .and_then(|time| chrono::DateTime::parse_from_rfc2822(&time).ok())
+            .map(|time| time.with_timezone(&chrono::Utc))
+    }
+
+    pub fn last_sign_in_date_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.last_sign_in_time()
+            .and_then(|time| chrono::DateTime::parse_from_rfc2822(&time).ok())
+            .map(|time| time.with_timezone(&chrono::Utc))
+    }
+}
+
+/// Times are UTC date strings, such as `"Tue, 14 Oct 2025 10:00:00 GMT"`.
 #[wasm_bindgen_struct]
-#[opts(module = "firestore/auth", getter)]
+#[opts(module = "firebase/auth", getter)]
 #[derive(Clone, Debug)]
 pub struct UserMetadata {
-    pub creation_time: String,
-    pub last_sign_in_time: String,
+    pub creation_time: Option<String>,
+    pub last_sign_in_time: Option<String>,
 }
 
 #[wasm_bindgen_struct]
@@ -42,18 +59,25 @@ pub struct IdTokenResult {
     pub issued_at_time: String,
     pub sign_in_provider: Option<String>,
     pub sign_in_second_factor: Option<String>,
-    pub token: Option<String>,
+    pub token: String,
     pub claims: ParsedToken,
 }
 
+impl IdTokenResult {
+    /// Every claim in the token, including custom claims, keyed by name.
+    pub fn claims_map(&self) -> Result<HashMap<String, serde_json::Value>, AuthError> {
+        Ok(serde_wasm_bindgen::from_value(self.claims().into())?)
+    }
+}
+
 #[wasm_bindgen_struct]
 #[opts(module = "firebase/auth", getter)]
 #[derive(Clone, Debug)]
 pub struct Firebase {
     #[opts(js_name = "sign_in_provider")]
-    pub sign_in_provider: Option<Firebase>,
+    pub sign_in_provider: Option<String>,
     #[opts(js_name = "sign_in_second_factor")]
-    pub sign_in_second_factor: Option<Firebase>,
+    pub sign_in_second_factor: Option<String>,
     pub identities: Option<js_sys::Object>,
 }
 
@@ -69,6 +93,9 @@ pub struct ParsedToken {
     pub firebase: Option<Firebase>,
 }
 
+/// Profile getters such as [`uid`](UserInfo::uid),
+/// [`email`](UserInfo::email) and [`display_name`](UserInfo::display_name)
+/// are available through [`UserInfo`], which this derefs to.
 #[wasm_bindgen_struct]
 #[opts(module = "firebase/auth", getter, extends = UserInfo)]
 #[derive(Clone, Debug)]
@@ -77,37 +104,47 @@ pub struct User {
     pub email_verified: bool,
     pub is_anonymous: bool,
     pub metadata: UserMetadata,
-    pub provider_data: Vec<UserInfo>,
     pub refresh_token: String,
-    pub tenant_id: String,
+    pub tenant_id: Option<String>,
 }
 
-#[wasm_bindgen_struct]
-#[opts(module = "firebase/auth")]
 impl User {
-    async fn delete(&self) -> MapValue<Result<(), JsValue>, Result<(), AuthError>> {
-        self.delete_js()
-            .await
-            .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+    /// See [`get_id_token`](super::get_id_token).
+    pub async fn get_id_token(&self, force_refresh: bool) -> Result<String, AuthError> {
+        super::get_id_token(self, force_refresh).await
     }
 
-    async fn get_id_token(
+    /// See [`get_id_token_result`](super::get_id_token_result).
+    pub async fn get_id_token_result(
         &self,
         force_refresh: bool,
-    ) -> MapValue<Result<JsValue, JsValue>, Result<String, JsValue>> {
-        self.get_id_token_js(force_refresh)
-            .await
-            .map(JsCast::unchecked_into::<js_sys::JsString>)
-            .map(|token| String::try_from(token).expect("token to be a string"))
+    ) -> Result<IdTokenResult, AuthError> {
+        super::get_id_token_result(self, force_refresh).await
     }
 
-    async fn get_id_token_result(
-        &self,
-        force_refresh: bool,
-    ) -> MapValue<Result<JsValue, JsValue>, Result<IdTokenResult, JsValue>> {
-        self.get_id_token_result_js(force_refresh)
+    /// Providers linked to the account, such as `"google.com"` or
+    /// `"password"`.
+    pub fn provider_data(&self) -> Vec<UserInfo> {
+        self.provider_data_js()
+            .iter()
+            .map(JsCast::unchecked_into::<UserInfo>)
+            .collect()
+    }
+}
+
+#[wasm_bindgen(module = "firebase/auth")]
+extern "C" {
+    #[wasm_bindgen(method, getter, js_name = providerData)]
+    fn provider_data_js(this: &User) -> js_sys::Array;
+}
+
+#[wasm_bindgen_struct]
+#[opts(module = "firebase/auth")]
+impl User {
+    async fn delete(&self) -> MapValue<Result<(), JsValue>, Result<(), AuthError>> {
+        self.delete_js()
             .await
-            .map(Into::into)
+            .map_err(|err| err.unchecked_into::<FirebaseError>().into())
     }
 
     async fn reload(&self) -> Result<(), JsValue>;
@@ -0,0 +1,448 @@
+mod bindings;
+pub mod server_value;
+
+use crate::{
+    stream::{try_callback_stream, CallbackStream},
+    FirebaseError, Unsubscribe, DESERIALIZATION_ERROR_CODE, SERIALIZATION_ERROR_CODE,
+};
+use bindings as b;
+pub use bindings::{
+    get_database, go_offline, go_online, limit_to_first, limit_to_last, on_disconnect,
+    order_by_child, order_by_key, order_by_value, push_ref, query, DataSnapshot, Database,
+    DatabaseQuery, DatabaseReference, OnDisconnect, QueryConstraint,
+};
+use futures::{channel::mpsc, Stream};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{cell::RefCell, error::Error, fmt, ops::ControlFlow, rc::Rc};
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[derive(Clone, Debug, derive_more::Deref)]
+pub struct DatabaseError {
+    pub kind: DatabaseErrorKind,
+    #[deref]
+    pub source: FirebaseError,
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl Error for DatabaseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl DatabaseError {
+    pub(crate) fn serialization(err: serde_wasm_bindgen::Error) -> Self {
+        Self {
+            kind: DatabaseErrorKind::Serialization,
+            source: FirebaseError::from_serde_error(err, SERIALIZATION_ERROR_CODE),
+        }
+    }
+
+    pub(crate) fn deserialization(err: serde_wasm_bindgen::Error) -> Self {
+        Self {
+            kind: DatabaseErrorKind::Deserialization,
+            source: FirebaseError::from_serde_error(err, DESERIALIZATION_ERROR_CODE),
+        }
+    }
+}
+
+impl From<FirebaseError> for DatabaseError {
+    fn from(err: FirebaseError) -> Self {
+        let code = err.code();
+        let kind = code
+            .parse()
+            .unwrap_or_else(|_| DatabaseErrorKind::Other(code));
+
+        Self { kind, source: err }
+    }
+}
+
+#[derive(Clone, Debug, strum_macros::EnumString)]
+#[non_exhaustive]
+pub enum DatabaseErrorKind {
+    #[strum(serialize = "PERMISSION_DENIED")]
+    PermissionDenied,
+    #[strum(serialize = "DISCONNECTED")]
+    Disconnected,
+    #[strum(serialize = "EXPIRED_TOKEN")]
+    ExpiredToken,
+    #[strum(serialize = "INVALID_TOKEN")]
+    InvalidToken,
+    #[strum(serialize = "MAX_RETRIES")]
+    MaxRetries,
+    #[strum(serialize = "NETWORK_ERROR")]
+    NetworkError,
+    #[strum(serialize = "OVERRIDDEN_BY_SET")]
+    OverriddenBySet,
+    #[strum(serialize = "UNAVAILABLE")]
+    Unavailable,
+    #[strum(serialize = "WRITE_CANCELED")]
+    WriteCanceled,
+    /// Data could not be converted to a JS value.
+    #[strum(serialize = "firebase-wasm/serialization")]
+    Serialization,
+    /// Data returned by the database could not be converted from a JS value.
+    #[strum(serialize = "firebase-wasm/deserialization")]
+    Deserialization,
+    #[strum(default)]
+    Other(String),
+}
+
+/// Returns a reference to the location at `path`, such as `"users/abc"`.
+///
+/// Panics if `path` contains `.`, `#`, `$`, `[` or `]`.
+pub fn ref_(db: &Database, path: &str) -> DatabaseReference {
+    b::ref_(db, path)
+}
+
+fn query_value<V: Serialize + ?Sized>(
+    value: &V,
+    key: Option<&str>,
+    constraint: fn(JsValue, Option<&str>) -> QueryConstraint,
+) -> Result<QueryConstraint, DatabaseError> {
+    let value = to_value(value)?;
+
+    Ok(constraint(value, key))
+}
+
+/// Starts the results at, and including, `value` in the query's order.
+///
+/// `key` breaks ties between children with the same value, by starting at
+/// that child's key.
+pub fn start_at<V: Serialize + ?Sized>(
+    value: &V,
+    key: Option<&str>,
+) -> Result<QueryConstraint, DatabaseError> {
+    query_value(value, key, b::start_at)
+}
+
+/// Ends the results at, and including, `value` in the query's order.
+///
+/// `key` breaks ties between children with the same value, by ending at
+/// that child's key.
+pub fn end_at<V: Serialize + ?Sized>(
+    value: &V,
+    key: Option<&str>,
+) -> Result<QueryConstraint, DatabaseError> {
+    query_value(value, key, b::end_at)
+}
+
+/// Only includes children equal to `value` in the query's order, or also
+/// with the key `key`.
+pub fn equal_to<V: Serialize + ?Sized>(
+    value: &V,
+    key: Option<&str>,
+) -> Result<QueryConstraint, DatabaseError> {
+    query_value(value, key, b::equal_to)
+}
+
+/// Serializes `value` with maps as plain objects and `None` as `null`, which
+/// the database treats as no value.
+fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, DatabaseError> {
+    let serializer = serde_wasm_bindgen::Serializer::new()
+        .serialize_maps_as_objects(true)
+        .serialize_missing_as_null(true);
+
+    value
+        .serialize(&serializer)
+        .map_err(DatabaseError::serialization)
+}
+
+fn snapshot_val<T: DeserializeOwned>(snapshot: &DataSnapshot) -> Result<Option<T>, DatabaseError> {
+    if !snapshot.exists() {
+        return Ok(None);
+    }
+
+    serde_wasm_bindgen::from_value(snapshot.val_js())
+        .map(Some)
+        .map_err(DatabaseError::deserialization)
+}
+
+/// Connects to the Realtime Database emulator at `host` and `port`, such as
+/// `"127.0.0.1"` and `9000`.
+///
+/// Must be called before any other database operation, otherwise it fails.
+pub fn connect_database_emulator(
+    db: &Database,
+    host: &str,
+    port: u16,
+) -> Result<(), DatabaseError> {
+    b::connect_database_emulator(db, host, port).map_err(Into::into)
+}
+
+/// Overwrites the data at `reference`, including any children.
+pub async fn set<T: Serialize + ?Sized>(
+    reference: &DatabaseReference,
+    value: &T,
+) -> Result<(), DatabaseError> {
+    let value = to_value(value)?;
+
+    b::set(reference, value)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Writes each of `updates`' fields to the child of `reference` at the
+/// field's key, atomically.
+///
+/// Keys are relative paths, such as `"posts/123/title"`, so that
+/// denormalized copies of data can be updated together. Other children are
+/// left untouched.
+pub async fn update<T: Serialize + ?Sized>(
+    reference: &DatabaseReference,
+    updates: &T,
+) -> Result<(), DatabaseError> {
+    let updates = to_value(updates)?;
+
+    b::update(reference, updates)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Deletes the data at `reference`, including any children.
+pub async fn remove(reference: &DatabaseReference) -> Result<(), DatabaseError> {
+    b::remove(reference)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Returns `None` if there is no data matching `query`.
+pub async fn get<T: DeserializeOwned>(query: &DatabaseQuery) -> Result<Option<T>, DatabaseError> {
+    let snapshot = b::get(query)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>())?
+        .unchecked_into::<DataSnapshot>();
+
+    snapshot_val(&snapshot)
+}
+
+/// Writes `value` to a new child of `parent`, returning the child's
+/// reference. Its [`key`](DatabaseReference::key) is a push ID, which sorts
+/// chronologically, such as for chat messages.
+pub async fn push<T: Serialize + ?Sized>(
+    parent: &DatabaseReference,
+    value: &T,
+) -> Result<DatabaseReference, DatabaseError> {
+    let reference = push_ref(parent);
+
+    set(&reference, value).await?;
+
+    Ok(reference)
+}
+
+impl DataSnapshot {
+    /// Returns `None` if the snapshot is empty.
+    pub fn val<T: DeserializeOwned>(&self) -> Result<Option<T>, DatabaseError> {
+        snapshot_val(self)
+    }
+
+    /// Calls `action` with each child, in the query's order, until it
+    /// returns [`ControlFlow::Break`].
+    ///
+    /// Unlike deserializing [`DataSnapshot::val`] into a map, this preserves
+    /// the query's order.
+    pub fn for_each(&self, mut action: impl FnMut(DataSnapshot) -> ControlFlow<()>) {
+        self.for_each_js(&mut |child| action(child).is_break());
+    }
+}
+
+impl OnDisconnect {
+    /// Overwrites the data at the reference on disconnect.
+    pub async fn set<T: Serialize + ?Sized>(&self, value: &T) -> Result<(), DatabaseError> {
+        let value = to_value(value)?;
+
+        self.set_js(value)
+            .await
+            .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+    }
+
+    /// Like [`OnDisconnect::set`], also setting the reference's priority.
+    pub async fn set_with_priority<T: Serialize + ?Sized, P: Serialize + ?Sized>(
+        &self,
+        value: &T,
+        priority: &P,
+    ) -> Result<(), DatabaseError> {
+        let value = to_value(value)?;
+        let priority = to_value(priority)?;
+
+        self.set_with_priority_js(value, priority)
+            .await
+            .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+    }
+
+    /// Like [`update`], on disconnect.
+    pub async fn update<T: Serialize + ?Sized>(&self, updates: &T) -> Result<(), DatabaseError> {
+        let updates = to_value(updates)?;
+
+        self.update_js(updates)
+            .await
+            .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+    }
+
+    /// Deletes the data at the reference on disconnect.
+    pub async fn remove(&self) -> Result<(), DatabaseError> {
+        self.remove_js()
+            .await
+            .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+    }
+
+    /// Cancels all writes registered for the reference and its children.
+    pub async fn cancel(&self) -> Result<(), DatabaseError> {
+        self.cancel_js()
+            .await
+            .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+    }
+}
+
+/// Stream of the data matching `query`, which stops listening when dropped.
+///
+/// Yields `None` whenever there is no matching data.
+pub fn on_value_stream<T: DeserializeOwned + 'static>(
+    query: &DatabaseQuery,
+) -> impl Stream<Item = Result<Option<T>, DatabaseError>> {
+    try_callback_stream(
+        |callback, cancel_callback| b::on_value(query, callback, cancel_callback),
+        |snapshot: DataSnapshot| snapshot_val(&snapshot),
+    )
+}
+
+/// A child's key, data, and the key of the sibling before it in the
+/// query's order, or `None` if it is first.
+pub type ChildEvent<T> = (String, T, Option<String>);
+
+type ChildEventFn = fn(
+    &DatabaseQuery,
+    &Closure<dyn FnMut(DataSnapshot, Option<String>)>,
+    &Closure<dyn FnMut(FirebaseError)>,
+) -> js_sys::Function;
+
+fn child_event_stream<T: DeserializeOwned + 'static>(
+    query: &DatabaseQuery,
+    on_child_event: ChildEventFn,
+) -> impl Stream<Item = Result<ChildEvent<T>, DatabaseError>> {
+    let (tx, rx) = mpsc::unbounded();
+
+    let callback = Closure::new(clone!(
+        [tx],
+        move |snapshot: DataSnapshot, previous_key: Option<String>| {
+            let event = serde_wasm_bindgen::from_value(snapshot.val_js())
+                .map(|data| (snapshot.key().unwrap_or_default(), data, previous_key))
+                .map_err(DatabaseError::deserialization);
+
+            tx.unbounded_send(event).ok();
+        }
+    ));
+    let cancel_callback = Closure::new(move |err: FirebaseError| {
+        tx.unbounded_send(Err(err.into())).ok();
+    });
+
+    let unsubscribe = on_child_event(query, &callback, &cancel_callback);
+
+    CallbackStream::new(
+        rx,
+        Unsubscribe::new(unsubscribe, (callback, cancel_callback)),
+    )
+}
+
+/// Stream of children added under `query`, starting with every existing
+/// child.
+pub fn on_child_added_stream<T: DeserializeOwned + 'static>(
+    query: &DatabaseQuery,
+) -> impl Stream<Item = Result<ChildEvent<T>, DatabaseError>> {
+    child_event_stream(query, b::on_child_added)
+}
+
+/// Stream of children of `query` whose data changed.
+pub fn on_child_changed_stream<T: DeserializeOwned + 'static>(
+    query: &DatabaseQuery,
+) -> impl Stream<Item = Result<ChildEvent<T>, DatabaseError>> {
+    child_event_stream(query, b::on_child_changed)
+}
+
+/// Stream of children removed from `query`, with their last data.
+///
+/// The previous key is always `None`.
+pub fn on_child_removed_stream<T: DeserializeOwned + 'static>(
+    query: &DatabaseQuery,
+) -> impl Stream<Item = Result<ChildEvent<T>, DatabaseError>> {
+    child_event_stream(query, b::on_child_removed)
+}
+
+/// Stream of children of `query` whose position in the query's order
+/// changed.
+pub fn on_child_moved_stream<T: DeserializeOwned + 'static>(
+    query: &DatabaseQuery,
+) -> impl Stream<Item = Result<ChildEvent<T>, DatabaseError>> {
+    child_event_stream(query, b::on_child_moved)
+}
+
+#[derive(Clone, Debug)]
+pub struct TransactionResult<T> {
+    /// `false` if the transaction was aborted.
+    pub committed: bool,
+    /// The data at the reference after the transaction.
+    pub value: Option<T>,
+}
+
+/// Atomically replaces the data at `reference` with the result of
+/// `transaction_update`, which is given the current data. Returning `None`
+/// aborts the transaction.
+///
+/// `transaction_update` is called again whenever the data is modified
+/// concurrently, and may first be called with locally cached data, so it
+/// must be free of side effects and idempotent.
+pub async fn run_transaction<F, T>(
+    reference: &DatabaseReference,
+    mut transaction_update: F,
+) -> Result<TransactionResult<T>, DatabaseError>
+where
+    F: FnMut(Option<T>) -> Option<T> + 'static,
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let update_err: Rc<RefCell<Option<DatabaseError>>> = Default::default();
+
+    let transaction_update = Closure::new(clone!([update_err], move |current: JsValue| {
+        let current = if current.is_null() || current.is_undefined() {
+            Ok(None)
+        } else {
+            serde_wasm_bindgen::from_value(current)
+                .map(Some)
+                .map_err(DatabaseError::deserialization)
+        };
+
+        let new = current.and_then(|current| {
+            transaction_update(current)
+                .as_ref()
+                .map(to_value)
+                .transpose()
+        });
+
+        match new {
+            Ok(new) => new.unwrap_or(JsValue::UNDEFINED),
+            Err(err) => {
+                *update_err.borrow_mut() = Some(err);
+
+                JsValue::UNDEFINED
+            }
+        }
+    }));
+
+    let result = b::run_transaction(reference, &transaction_update)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>())?
+        .unchecked_into::<b::TransactionResult>();
+
+    if let Some(err) = update_err.borrow_mut().take() {
+        return Err(err);
+    }
+
+    Ok(TransactionResult {
+        committed: result.committed(),
+        value: snapshot_val(&result.snapshot())?,
+    })
+}
@@ -0,0 +1,227 @@
+use crate::{app::FirebaseApp, FirebaseError};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(module = "firebase/database")]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type Database;
+    #[wasm_bindgen(js_name = Query)]
+    #[derive(Clone, Debug)]
+    pub type DatabaseQuery;
+    #[wasm_bindgen(extends = DatabaseQuery)]
+    #[derive(Clone, Debug)]
+    pub type DatabaseReference;
+    #[derive(Clone, Debug)]
+    pub type QueryConstraint;
+    #[derive(Clone, Debug)]
+    pub(crate) type TransactionResult;
+    /// Each write is registered with the server once its future resolves,
+    /// and runs even if the app is closed.
+    #[derive(Clone, Debug)]
+    pub type OnDisconnect;
+    #[derive(Clone, Debug)]
+    pub type DataSnapshot;
+
+    /// Returns the [`Database`] instance of `app`, or of the default app
+    /// when `None`.
+    #[wasm_bindgen(js_name = getDatabase)]
+    pub fn get_database(app: Option<&FirebaseApp>) -> Database;
+
+    /// Disconnects from the server. Reads are served from the cache and
+    /// writes are queued until [`go_online`] is called.
+    #[wasm_bindgen(js_name = goOffline)]
+    pub fn go_offline(db: &Database);
+
+    /// Reconnects to the server after [`go_offline`].
+    #[wasm_bindgen(js_name = goOnline)]
+    pub fn go_online(db: &Database);
+
+    #[wasm_bindgen(js_name = connectDatabaseEmulator, catch)]
+    pub(crate) fn connect_database_emulator(
+        db: &Database,
+        host: &str,
+        port: u16,
+    ) -> Result<(), FirebaseError>;
+
+    #[wasm_bindgen(js_name = "ref")]
+    pub fn ref_(db: &Database, path: &str) -> DatabaseReference;
+
+    /// Returns a child of `parent` with a new, time-ordered push ID as its
+    /// key, without writing anything.
+    #[wasm_bindgen(js_name = push)]
+    pub fn push_ref(parent: &DatabaseReference) -> DatabaseReference;
+
+    #[wasm_bindgen(catch)]
+    pub async fn set(reference: &DatabaseReference, value: JsValue) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch)]
+    pub async fn update(reference: &DatabaseReference, values: JsValue) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch)]
+    pub async fn remove(reference: &DatabaseReference) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch)]
+    pub async fn get(query: &DatabaseQuery) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = onValue)]
+    pub(crate) fn on_value(
+        query: &DatabaseQuery,
+        callback: &Closure<dyn FnMut(DataSnapshot)>,
+        cancel_callback: &Closure<dyn FnMut(FirebaseError)>,
+    ) -> js_sys::Function;
+
+    #[wasm_bindgen(js_name = onChildAdded)]
+    pub(crate) fn on_child_added(
+        query: &DatabaseQuery,
+        callback: &Closure<dyn FnMut(DataSnapshot, Option<String>)>,
+        cancel_callback: &Closure<dyn FnMut(FirebaseError)>,
+    ) -> js_sys::Function;
+
+    #[wasm_bindgen(js_name = onChildChanged)]
+    pub(crate) fn on_child_changed(
+        query: &DatabaseQuery,
+        callback: &Closure<dyn FnMut(DataSnapshot, Option<String>)>,
+        cancel_callback: &Closure<dyn FnMut(FirebaseError)>,
+    ) -> js_sys::Function;
+
+    #[wasm_bindgen(js_name = onChildRemoved)]
+    pub(crate) fn on_child_removed(
+        query: &DatabaseQuery,
+        callback: &Closure<dyn FnMut(DataSnapshot, Option<String>)>,
+        cancel_callback: &Closure<dyn FnMut(FirebaseError)>,
+    ) -> js_sys::Function;
+
+    #[wasm_bindgen(js_name = onChildMoved)]
+    pub(crate) fn on_child_moved(
+        query: &DatabaseQuery,
+        callback: &Closure<dyn FnMut(DataSnapshot, Option<String>)>,
+        cancel_callback: &Closure<dyn FnMut(FirebaseError)>,
+    ) -> js_sys::Function;
+
+    /// Returns a handle for writes to `reference` that the server runs when
+    /// this client disconnects, such as to mark a user as offline.
+    #[wasm_bindgen(js_name = onDisconnect)]
+    pub fn on_disconnect(reference: &DatabaseReference) -> OnDisconnect;
+
+    #[wasm_bindgen(js_name = runTransaction, catch)]
+    pub(crate) async fn run_transaction(
+        reference: &DatabaseReference,
+        transaction_update: &Closure<dyn FnMut(JsValue) -> JsValue>,
+    ) -> Result<JsValue, JsValue>;
+
+    /// Narrows or orders `query` with `constraints`, such as
+    /// `vec![order_by_child("ts"), limit_to_last(50)]` for the 50 most
+    /// recent children.
+    #[wasm_bindgen(variadic)]
+    pub fn query(query: &DatabaseQuery, constraints: Vec<QueryConstraint>) -> DatabaseQuery;
+
+    /// Orders by the value of the child at `path`, such as `"ts"`.
+    #[wasm_bindgen(js_name = orderByChild)]
+    pub fn order_by_child(path: &str) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = orderByKey)]
+    pub fn order_by_key() -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = orderByValue)]
+    pub fn order_by_value() -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = limitToFirst)]
+    pub fn limit_to_first(limit: u32) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = limitToLast)]
+    pub fn limit_to_last(limit: u32) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = startAt)]
+    pub(crate) fn start_at(value: JsValue, key: Option<&str>) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = endAt)]
+    pub(crate) fn end_at(value: JsValue, key: Option<&str>) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = equalTo)]
+    pub(crate) fn equal_to(value: JsValue, key: Option<&str>) -> QueryConstraint;
+
+    // =========================================================================
+    //                            DatabaseReference
+    // =========================================================================
+
+    /// The last segment of the path, or `None` for the root.
+    #[wasm_bindgen(method, getter)]
+    pub fn key(this: &DatabaseReference) -> Option<String>;
+
+    #[wasm_bindgen(method, js_name = isEqual)]
+    pub fn is_equal(this: &DatabaseReference, other: &DatabaseReference) -> bool;
+
+    // =========================================================================
+    //                           TransactionResult
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter)]
+    pub(crate) fn committed(this: &TransactionResult) -> bool;
+
+    #[wasm_bindgen(method, getter)]
+    pub(crate) fn snapshot(this: &TransactionResult) -> DataSnapshot;
+
+    // =========================================================================
+    //                              OnDisconnect
+    // =========================================================================
+
+    #[wasm_bindgen(method, js_name = set, catch)]
+    pub(crate) async fn set_js(this: &OnDisconnect, value: JsValue) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, js_name = setWithPriority, catch)]
+    pub(crate) async fn set_with_priority_js(
+        this: &OnDisconnect,
+        value: JsValue,
+        priority: JsValue,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, js_name = update, catch)]
+    pub(crate) async fn update_js(this: &OnDisconnect, values: JsValue) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, js_name = remove, catch)]
+    pub(crate) async fn remove_js(this: &OnDisconnect) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, js_name = cancel, catch)]
+    pub(crate) async fn cancel_js(this: &OnDisconnect) -> Result<(), JsValue>;
+
+    // =========================================================================
+    //                              DataSnapshot
+    // =========================================================================
+
+    /// The last segment of the snapshot's path, or `None` for the root.
+    #[wasm_bindgen(method, getter)]
+    pub fn key(this: &DataSnapshot) -> Option<String>;
+
+    #[wasm_bindgen(method)]
+    pub fn exists(this: &DataSnapshot) -> bool;
+
+    #[wasm_bindgen(method, js_name = val)]
+    pub(crate) fn val_js(this: &DataSnapshot) -> JsValue;
+
+    /// Returns the snapshot of the child at the relative `path`, such as
+    /// `"name/first"`, which is empty if the child does not exist.
+    #[wasm_bindgen(method)]
+    pub fn child(this: &DataSnapshot, path: &str) -> DataSnapshot;
+
+    #[wasm_bindgen(method, js_name = hasChild)]
+    pub fn has_child(this: &DataSnapshot, path: &str) -> bool;
+
+    #[wasm_bindgen(method, js_name = hasChildren)]
+    pub fn has_children(this: &DataSnapshot) -> bool;
+
+    #[wasm_bindgen(method, getter, js_name = size)]
+    pub fn num_children(this: &DataSnapshot) -> u32;
+
+    #[wasm_bindgen(method, js_name = forEach)]
+    pub(crate) fn for_each_js(
+        this: &DataSnapshot,
+        action: &mut dyn FnMut(DataSnapshot) -> bool,
+    ) -> bool;
+}
+
+impl PartialEq for DatabaseReference {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_equal(other)
+    }
+}
+impl Eq for DatabaseReference {}
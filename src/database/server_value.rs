@@ -0,0 +1,32 @@
+//! Placeholder values that the server fills in, for use in writes such as
+//! [`set`](super::set) and [`update`](super::update).
+
+use serde::{Serialize, Serializer};
+use wasm_bindgen::prelude::*;
+
+/// Only serializes with `serde_wasm_bindgen`, which passes the value through
+/// as-is.
+impl Serialize for ServerValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_wasm_bindgen::preserve::serialize(self, serializer)
+    }
+}
+
+#[wasm_bindgen(module = "firebase/database")]
+extern "C" {
+    /// The SDK represents these as plain objects, so there is no class to
+    /// check against.
+    #[wasm_bindgen(is_type_of = JsValue::is_object)]
+    #[derive(Clone, Debug)]
+    pub type ServerValue;
+
+    /// Replaced with the server's time, in milliseconds since the Unix
+    /// epoch, when written.
+    #[wasm_bindgen(js_name = serverTimestamp)]
+    pub fn timestamp() -> ServerValue;
+
+    /// Increments a numeric value by `delta`, or sets it to `delta` if it is
+    /// not a number.
+    #[wasm_bindgen]
+    pub fn increment(delta: f64) -> ServerValue;
+}
@@ -1,13 +1,21 @@
 mod bindings;
+pub mod field_value;
 
-use crate::FirebaseError;
+use crate::{
+    app::FirebaseApp, stream::try_callback_stream, FirebaseError, Unsubscribe,
+    DESERIALIZATION_ERROR_CODE, SERIALIZATION_ERROR_CODE,
+};
 use bindings as b;
+#[cfg(feature = "chrono")]
+pub use bindings::TimestampOutOfRange;
 pub use bindings::{
-    add_doc, delete_doc, doc, get_firestore, limit, on_snapshot_doc, on_snapshot_query, query,
-    set_doc, update_doc, CollectionReference, DocumentReference, DocumentSnapshot, Firestore,
-    Query, QueryConstraint, QuerySnapshot, SetDocOptions, Timestamp, Transaction,
+    get_firestore, limit, limit_to_last, query, query_equal, query_snapshot_equal, ref_equal,
+    snapshot_equal, Bytes, CollectionReference, DocumentChange, DocumentReference,
+    DocumentSnapshot, FieldPath, Firestore, GeoPoint, Query, QueryConstraint, QuerySnapshot,
+    SetDocOptions, SnapshotMetadata, Timestamp, Transaction,
 };
-use futures::Future;
+use futures::{Future, Stream};
+use serde::{de::DeserializeOwned, Serialize};
 use std::{cell::RefCell, error::Error, fmt, rc::Rc, time::SystemTime};
 use wasm_bindgen::{
     prelude::{Closure, *},
@@ -36,9 +44,28 @@ impl Error for FirestoreError {
     }
 }
 
+impl FirestoreError {
+    pub(crate) fn serialization(err: serde_wasm_bindgen::Error) -> Self {
+        Self {
+            kind: FirestoreErrorKind::Serialization,
+            source: FirebaseError::from_serde_error(err, SERIALIZATION_ERROR_CODE),
+        }
+    }
+
+    pub(crate) fn deserialization(err: serde_wasm_bindgen::Error) -> Self {
+        Self {
+            kind: FirestoreErrorKind::Deserialization,
+            source: FirebaseError::from_serde_error(err, DESERIALIZATION_ERROR_CODE),
+        }
+    }
+}
+
 impl From<FirebaseError> for FirestoreError {
     fn from(err: FirebaseError) -> Self {
-        let kind = err.code().parse().unwrap();
+        let code = err.code();
+        let kind = code
+            .parse()
+            .unwrap_or_else(|_| FirestoreErrorKind::Other(code));
 
         Self { kind, source: err }
     }
@@ -79,6 +106,12 @@ pub enum FirestoreErrorKind {
     DataLoss,
     #[strum(serialize = "unauthenticated")]
     Unauthenticated,
+    /// Data could not be converted to a JS value.
+    #[strum(serialize = "firebase-wasm/serialization")]
+    Serialization,
+    /// Data returned by Firestore could not be converted from a JS value.
+    #[strum(serialize = "firebase-wasm/deserialization")]
+    Deserialization,
     #[strum(default)]
     Other(String),
 }
@@ -89,9 +122,48 @@ pub enum Direction {
     Desc,
 }
 
-pub fn order_by(field_path: &str, direction: Direction) -> QueryConstraint {
+impl FieldPath {
+    /// The ID of a document, to filter or order by with [`where_`] and
+    /// [`order_by`], such as to fetch several documents by ID with
+    /// [`WhereFilterOp::In`].
+    pub fn document_id() -> Self {
+        b::document_id()
+    }
+}
+
+/// A field to filter or order by: either a dot-separated path, such as
+/// `"address.city"`, or a [`FieldPath`].
+pub trait IntoFieldPath {
+    fn into_field_path(self) -> JsValue;
+}
+
+impl IntoFieldPath for &str {
+    fn into_field_path(self) -> JsValue {
+        self.into()
+    }
+}
+
+impl IntoFieldPath for &String {
+    fn into_field_path(self) -> JsValue {
+        self.into()
+    }
+}
+
+impl IntoFieldPath for FieldPath {
+    fn into_field_path(self) -> JsValue {
+        self.into()
+    }
+}
+
+impl IntoFieldPath for &FieldPath {
+    fn into_field_path(self) -> JsValue {
+        self.clone().into()
+    }
+}
+
+pub fn order_by(field_path: impl IntoFieldPath, direction: Direction) -> QueryConstraint {
     b::order_by(
-        field_path,
+        &field_path.into_field_path(),
         match direction {
             Direction::Asc => "asc",
             Direction::Desc => "desc",
@@ -99,18 +171,97 @@ pub fn order_by(field_path: &str, direction: Direction) -> QueryConstraint {
     )
 }
 
-pub fn where_<V: Into<JsValue>>(
-    field_path: &str,
-    op: QueryConstraintOp,
-    value: V,
-) -> QueryConstraint {
-    let value = value.into();
+pub fn where_<V: Serialize + ?Sized>(
+    field_path: impl IntoFieldPath,
+    op: WhereFilterOp,
+    value: &V,
+) -> Result<QueryConstraint, FirestoreError> {
+    let value = to_value(value)?;
+
+    Ok(b::where_(
+        &field_path.into_field_path(),
+        &op.to_string(),
+        value,
+    ))
+}
+
+/// Where a query cursor is positioned, for use with [`start_at`],
+/// [`start_after`], [`end_at`] and [`end_before`].
+#[derive(Debug)]
+pub enum Cursor<'a, V: ?Sized> {
+    /// Field values, in the same order as the query's [`order_by`]
+    /// constraints. `V` should serialize to a sequence, such as a tuple or
+    /// slice; any other value is used as the only field value.
+    Values(&'a V),
+    /// A document from a previous query, which avoids skipping or
+    /// duplicating documents whose ordered fields tie.
+    Snapshot(&'a DocumentSnapshot),
+}
 
-    b::where_(field_path, &op.to_string(), value)
+impl<V: ?Sized> Clone for Cursor<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
-pub enum QueryConstraintOp {
-    /// `<`o
+impl<V: ?Sized> Copy for Cursor<'_, V> {}
+
+impl<'a> Cursor<'a, ()> {
+    /// Shorthand for [`Cursor::Snapshot`], which fixes the otherwise unused
+    /// `V`.
+    pub fn snapshot(snapshot: &'a DocumentSnapshot) -> Self {
+        Self::Snapshot(snapshot)
+    }
+}
+
+fn cursor<V: Serialize + ?Sized>(
+    cursor: Cursor<V>,
+    with_values: fn(Vec<JsValue>) -> QueryConstraint,
+    with_snapshot: fn(&DocumentSnapshot) -> QueryConstraint,
+) -> Result<QueryConstraint, FirestoreError> {
+    match cursor {
+        Cursor::Values(values) => {
+            let values = match to_value(values)?.dyn_into::<js_sys::Array>() {
+                Ok(values) => values.to_vec(),
+                Err(value) => vec![value],
+            };
+
+            Ok(with_values(values))
+        }
+        Cursor::Snapshot(snapshot) => Ok(with_snapshot(snapshot)),
+    }
+}
+
+/// Starts the query results at, and including, `at`.
+pub fn start_at<V: Serialize + ?Sized>(at: Cursor<V>) -> Result<QueryConstraint, FirestoreError> {
+    cursor(at, b::start_at, b::start_at_snapshot)
+}
+
+/// Starts the query results just after `after`.
+pub fn start_after<V: Serialize + ?Sized>(
+    after: Cursor<V>,
+) -> Result<QueryConstraint, FirestoreError> {
+    cursor(after, b::start_after, b::start_after_snapshot)
+}
+
+/// Ends the query results at, and including, `at`.
+pub fn end_at<V: Serialize + ?Sized>(at: Cursor<V>) -> Result<QueryConstraint, FirestoreError> {
+    cursor(at, b::end_at, b::end_at_snapshot)
+}
+
+/// Ends the query results just before `before`.
+pub fn end_before<V: Serialize + ?Sized>(
+    before: Cursor<V>,
+) -> Result<QueryConstraint, FirestoreError> {
+    cursor(before, b::end_before, b::end_before_snapshot)
+}
+
+#[deprecated = "renamed to `WhereFilterOp`"]
+pub type QueryConstraintOp = WhereFilterOp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhereFilterOp {
+    /// `<`
     LessThan,
     /// `<=`
     LessThanEq,
@@ -132,7 +283,7 @@ pub enum QueryConstraintOp {
     NotIn,
 }
 
-impl fmt::Display for QueryConstraintOp {
+impl fmt::Display for WhereFilterOp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let str = match self {
             Self::LessThan => "<",
@@ -151,55 +302,662 @@ impl fmt::Display for QueryConstraintOp {
     }
 }
 
-pub async fn get_doc(doc: DocumentReference) -> Result<DocumentSnapshot, FirestoreError> {
-    b::get_doc(doc)
+pub fn on_snapshot_doc(
+    reference: &DocumentReference,
+    observer: Closure<dyn FnMut(DocumentSnapshot)>,
+) -> Unsubscribe {
+    let unsubscribe = b::on_snapshot_doc(reference, &observer);
+
+    Unsubscribe::new(unsubscribe, observer)
+}
+
+pub fn on_snapshot_query(
+    query: &Query,
+    observer: Closure<dyn FnMut(QuerySnapshot)>,
+) -> Unsubscribe {
+    let unsubscribe = b::on_snapshot_query(query, &observer);
+
+    Unsubscribe::new(unsubscribe, observer)
+}
+
+/// Stream of a document's data, which stops listening when dropped.
+///
+/// Yields `None` whenever the document does not exist.
+pub fn doc_stream<T: DeserializeOwned + 'static>(
+    doc: &DocumentReference,
+) -> impl Stream<Item = Result<Option<T>, FirestoreError>> {
+    try_callback_stream(
+        |on_next, on_error| b::on_snapshot_doc_with_error(doc, on_next, on_error),
+        |snapshot: DocumentSnapshot| snapshot_data(&snapshot),
+    )
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryUpdate<T> {
+    /// Every document in the query, paired with its ID.
+    pub docs: Vec<(String, T)>,
+    /// Changes since the previous update.
+    pub changes: Vec<DocChange<T>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DocChange<T> {
+    pub kind: DocChangeKind,
+    pub id: String,
+    pub data: T,
+    /// `None` for [`DocChangeKind::Added`].
+    pub old_index: Option<usize>,
+    /// `None` for [`DocChangeKind::Removed`].
+    pub new_index: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::EnumString)]
+pub enum DocChangeKind {
+    #[strum(serialize = "added")]
+    Added,
+    #[strum(serialize = "modified")]
+    Modified,
+    #[strum(serialize = "removed")]
+    Removed,
+}
+
+/// Stream of a query's results and the changes between them, which stops
+/// listening when dropped.
+pub fn query_stream<T: DeserializeOwned + 'static>(
+    query: &Query,
+) -> impl Stream<Item = Result<QueryUpdate<T>, FirestoreError>> {
+    try_callback_stream(
+        |on_next, on_error| b::on_snapshot_query_with_error(query, on_next, on_error),
+        |snapshot: QuerySnapshot| query_update(&snapshot),
+    )
+}
+
+fn query_update<T: DeserializeOwned>(
+    snapshot: &QuerySnapshot,
+) -> Result<QueryUpdate<T>, FirestoreError> {
+    let changes = snapshot
+        .doc_changes()
+        .into_iter()
+        .map(|change| {
+            let doc = change.doc();
+            let type_ = change.type_();
+            let kind = type_.parse().map_err(|_| {
+                FirestoreError::deserialization(serde::de::Error::custom(format!(
+                    "unknown document change type `{type_}`"
+                )))
+            })?;
+
+            Ok(DocChange {
+                kind,
+                id: doc.id(),
+                data: serde_wasm_bindgen::from_value(doc.data())
+                    .map_err(FirestoreError::deserialization)?,
+                old_index: change.old_index().try_into().ok(),
+                new_index: change.new_index().try_into().ok(),
+            })
+        })
+        .collect::<Result<_, FirestoreError>>()?;
+
+    Ok(QueryUpdate {
+        docs: query_docs(snapshot)?,
+        changes,
+    })
+}
+
+fn snapshot_data<T: DeserializeOwned>(
+    snapshot: &DocumentSnapshot,
+) -> Result<Option<T>, FirestoreError> {
+    if !snapshot.exists() {
+        return Ok(None);
+    }
+
+    serde_wasm_bindgen::from_value(snapshot.data())
+        .map(Some)
+        .map_err(FirestoreError::deserialization)
+}
+
+/// Where reads get their data from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Source {
+    /// The server when online, falling back to the cache when offline.
+    #[default]
+    Default,
+    /// Only the local cache, failing if the data is not cached.
+    Cache,
+    /// Only the server, failing if offline.
+    Server,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GetOptions {
+    pub source: Source,
+}
+
+/// Returns `None` if the document does not exist.
+pub async fn get_doc<T: DeserializeOwned>(
+    doc: &DocumentReference,
+) -> Result<Option<T>, FirestoreError> {
+    get_doc_with_options(doc, GetOptions::default()).await
+}
+
+/// Returns `None` if the document does not exist.
+pub async fn get_doc_with_options<T: DeserializeOwned>(
+    doc: &DocumentReference,
+    options: GetOptions,
+) -> Result<Option<T>, FirestoreError> {
+    let snapshot = get_doc_snapshot_with_options(doc, options).await?;
+
+    snapshot_data(&snapshot)
+}
+
+pub async fn get_doc_snapshot(doc: &DocumentReference) -> Result<DocumentSnapshot, FirestoreError> {
+    get_doc_snapshot_with_options(doc, GetOptions::default()).await
+}
+
+pub async fn get_doc_snapshot_with_options(
+    doc: &DocumentReference,
+    options: GetOptions,
+) -> Result<DocumentSnapshot, FirestoreError> {
+    match options.source {
+        Source::Default => b::get_doc(doc).await,
+        Source::Cache => b::get_doc_from_cache(doc).await,
+        Source::Server => b::get_doc_from_server(doc).await,
+    }
+    .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+    .map(|snapshot| snapshot.unchecked_into())
+}
+
+/// Returns each document's ID paired with its data.
+pub async fn get_docs<T: DeserializeOwned>(
+    query: &Query,
+) -> Result<Vec<(String, T)>, FirestoreError> {
+    get_docs_with_options(query, GetOptions::default()).await
+}
+
+/// Returns each document's ID paired with its data.
+pub async fn get_docs_with_options<T: DeserializeOwned>(
+    query: &Query,
+    options: GetOptions,
+) -> Result<Vec<(String, T)>, FirestoreError> {
+    let snapshot = get_docs_snapshot_with_options(query, options).await?;
+
+    query_docs(&snapshot)
+}
+
+fn query_docs<T: DeserializeOwned>(
+    snapshot: &QuerySnapshot,
+) -> Result<Vec<(String, T)>, FirestoreError> {
+    snapshot
+        .docs()
+        .into_iter()
+        .map(|doc| {
+            serde_wasm_bindgen::from_value(doc.data())
+                .map(|data| (doc.id(), data))
+                .map_err(FirestoreError::deserialization)
+        })
+        .collect()
+}
+
+/// Returns the number of documents matching `query`, counted by the server
+/// without downloading them.
+pub async fn get_count(query: &Query) -> Result<u64, FirestoreError> {
+    let snapshot = b::get_count_from_server(query)
         .await
-        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
-        .map(|snapshot| snapshot.unchecked_into())
+        .map_err(|err| err.unchecked_into::<FirebaseError>())?
+        .unchecked_into::<b::AggregateQuerySnapshot>();
+
+    let count =
+        js_sys::Reflect::get(&snapshot.data(), &"count".into()).unwrap_or(JsValue::UNDEFINED);
+
+    serde_wasm_bindgen::from_value(count).map_err(FirestoreError::deserialization)
+}
+
+/// A server-side aggregation, whose result is keyed by `alias` in
+/// [`AggregateResult`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AggregateSpec {
+    pub alias: String,
+    pub kind: AggregateKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AggregateKind {
+    /// The number of matching documents.
+    Count,
+    /// The sum of a numeric field.
+    Sum(String),
+    /// The average of a numeric field.
+    Average(String),
 }
 
-pub async fn get_docs(query: Query) -> Result<QuerySnapshot, FirestoreError> {
-    b::get_docs(query)
+impl AggregateSpec {
+    pub fn count(alias: impl Into<String>) -> Self {
+        Self {
+            alias: alias.into(),
+            kind: AggregateKind::Count,
+        }
+    }
+
+    pub fn sum(alias: impl Into<String>, field: impl Into<String>) -> Self {
+        Self {
+            alias: alias.into(),
+            kind: AggregateKind::Sum(field.into()),
+        }
+    }
+
+    pub fn average(alias: impl Into<String>, field: impl Into<String>) -> Self {
+        Self {
+            alias: alias.into(),
+            kind: AggregateKind::Average(field.into()),
+        }
+    }
+}
+
+/// The results of [`get_aggregate`], keyed by [`AggregateSpec::alias`].
+#[derive(Clone, Debug)]
+pub struct AggregateResult {
+    data: js_sys::Object,
+}
+
+impl AggregateResult {
+    /// Returns `None` if there is no aggregation named `alias`, or if it has
+    /// no value, such as the average of no documents.
+    pub fn get(&self, alias: &str) -> Option<f64> {
+        js_sys::Reflect::get(&self.data, &alias.into())
+            .ok()
+            .and_then(|value| value.as_f64())
+    }
+
+    /// Returns the result of a [`AggregateKind::Count`] aggregation.
+    pub fn count(&self, alias: &str) -> Option<u64> {
+        self.get(alias).map(|count| count as u64)
+    }
+}
+
+/// Runs the aggregations in `specs` over the documents matching `query` on
+/// the server, without downloading them.
+pub async fn get_aggregate(
+    query: &Query,
+    specs: &[AggregateSpec],
+) -> Result<AggregateResult, FirestoreError> {
+    let aggregate_spec = js_sys::Object::new();
+
+    for spec in specs {
+        let field = match &spec.kind {
+            AggregateKind::Count => b::count(),
+            AggregateKind::Sum(field) => b::sum(field),
+            AggregateKind::Average(field) => b::average(field),
+        };
+
+        js_sys::Reflect::set(&aggregate_spec, &spec.alias.as_str().into(), &field).unwrap();
+    }
+
+    let snapshot = b::get_aggregate_from_server(query, &aggregate_spec)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>())?
+        .unchecked_into::<b::AggregateQuerySnapshot>();
+
+    Ok(AggregateResult {
+        data: snapshot.data(),
+    })
+}
+
+pub async fn get_docs_snapshot(query: &Query) -> Result<QuerySnapshot, FirestoreError> {
+    get_docs_snapshot_with_options(query, GetOptions::default()).await
+}
+
+pub async fn get_docs_snapshot_with_options(
+    query: &Query,
+    options: GetOptions,
+) -> Result<QuerySnapshot, FirestoreError> {
+    match options.source {
+        Source::Default => b::get_docs(query).await,
+        Source::Cache => b::get_docs_from_cache(query).await,
+        Source::Server => b::get_docs_from_server(query).await,
+    }
+    .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+    .map(|snapshot| snapshot.unchecked_into())
+}
+
+/// Serializes `data` with maps as plain objects, which is what Firestore
+/// expects.
+fn to_value<T: Serialize + ?Sized>(data: &T) -> Result<JsValue, FirestoreError> {
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+
+    data.serialize(&serializer)
+        .map_err(FirestoreError::serialization)
+}
+
+/// Overwrites the document with `data`.
+///
+/// `None` fields serialize to `undefined`, which Firestore rejects, so they
+/// should be omitted with `#[serde_with::skip_serializing_none]`.
+pub async fn set_doc<T: Serialize + ?Sized>(
+    doc: &DocumentReference,
+    data: &T,
+) -> Result<(), FirestoreError> {
+    b::set_doc(doc, to_value(data)?)
         .await
         .map_err(|err| err.unchecked_into::<FirebaseError>().into())
-        .map(|snapshot| snapshot.unchecked_into())
 }
 
-pub async fn set_doc_with_options<D: Into<JsValue>>(
-    doc: DocumentReference,
-    data: D,
+/// Merges `data` into the document, leaving fields not present in `data`
+/// untouched.
+pub async fn set_doc_merge<T: Serialize + ?Sized>(
+    doc: &DocumentReference,
+    data: &T,
+) -> Result<(), FirestoreError> {
+    set_doc_with_options(doc, data, SetDocOptions { merge: Some(true) }).await
+}
+
+pub async fn set_doc_with_options<T: Serialize + ?Sized>(
+    doc: &DocumentReference,
+    data: &T,
     options: SetDocOptions,
 ) -> Result<(), FirestoreError> {
-    b::set_doc_with_options(doc, data.into(), options)
+    b::set_doc_with_options(doc, to_value(data)?, options)
         .await
         .map_err(|err| err.unchecked_into::<FirebaseError>().into())
 }
 
-pub fn collection(firestore: Firestore, path: &str) -> Result<CollectionReference, FirestoreError> {
+/// Updates fields of an existing document, failing with
+/// [`FirestoreErrorKind::NotFound`] if it does not exist.
+///
+/// Keys are passed through verbatim, so dotted field paths such as
+/// `"address.city"` can be used in maps to update nested fields.
+pub async fn update_doc<T: Serialize + ?Sized>(
+    doc: &DocumentReference,
+    data: &T,
+) -> Result<(), FirestoreError> {
+    b::update_doc(doc, to_value(data)?)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+pub async fn delete_doc(doc: &DocumentReference) -> Result<(), FirestoreError> {
+    b::delete_doc(doc)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Creates a document with an auto-generated ID, returning its reference.
+pub async fn add_doc<T: Serialize + ?Sized>(
+    collection: &CollectionReference,
+    data: &T,
+) -> Result<DocumentReference, FirestoreError> {
+    b::add_doc(collection, to_value(data)?)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+        .map(|doc| doc.unchecked_into())
+}
+
+/// `path` is the slash-separated path to the document, such as
+/// `"users/abc"`.
+pub fn doc(firestore: &Firestore, path: &str) -> Result<DocumentReference, FirestoreError> {
+    b::doc(firestore, path).map_err(Into::into)
+}
+
+pub fn collection(
+    firestore: &Firestore,
+    path: &str,
+) -> Result<CollectionReference, FirestoreError> {
     b::collection(firestore, path).map_err(|err| err.into())
 }
 
+/// Settings for [`initialize_firestore`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FirestoreSettings {
+    /// Defaults to [`LocalCache::Memory`].
+    pub local_cache: Option<LocalCache>,
+    /// Skips `undefined` fields, such as `None` fields, instead of rejecting
+    /// the write.
+    pub ignore_undefined_properties: Option<bool>,
+}
+
+/// Where Firestore caches documents locally.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LocalCache {
+    /// Cached documents are lost when the page is closed.
+    Memory,
+    /// Cached documents are kept in IndexedDB, so the app can work offline
+    /// across reloads.
+    Persistent { tab_manager: TabManager },
+}
+
+/// Which tabs can share a [`LocalCache::Persistent`] cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TabManager {
+    /// Only one tab at a time can use the cache.
+    #[default]
+    SingleTab,
+    /// All tabs of the app share the cache.
+    MultipleTabs,
+}
+
+/// Initializes Firestore for `app` with `settings`.
+///
+/// This must be called before any other use of Firestore for `app`,
+/// including [`get_firestore`].
+pub fn initialize_firestore(
+    app: &FirebaseApp,
+    settings: FirestoreSettings,
+) -> Result<Firestore, FirestoreError> {
+    let js_settings = js_sys::Object::new();
+
+    if let Some(local_cache) = settings.local_cache {
+        let local_cache = match local_cache {
+            LocalCache::Memory => b::memory_local_cache(),
+            LocalCache::Persistent { tab_manager } => {
+                let cache_settings = js_sys::Object::new();
+
+                let tab_manager = match tab_manager {
+                    TabManager::SingleTab => b::persistent_single_tab_manager(),
+                    TabManager::MultipleTabs => b::persistent_multiple_tab_manager(),
+                };
+
+                js_sys::Reflect::set(&cache_settings, &"tabManager".into(), &tab_manager).unwrap();
+
+                b::persistent_local_cache(&cache_settings)
+            }
+        };
+
+        js_sys::Reflect::set(&js_settings, &"localCache".into(), &local_cache).unwrap();
+    }
+
+    if let Some(ignore_undefined_properties) = settings.ignore_undefined_properties {
+        js_sys::Reflect::set(
+            &js_settings,
+            &"ignoreUndefinedProperties".into(),
+            &ignore_undefined_properties.into(),
+        )
+        .unwrap();
+    }
+
+    b::initialize_firestore(app, &js_settings).map_err(Into::into)
+}
+
+/// Enables IndexedDB persistence with the legacy API.
+///
+/// Prefer [`initialize_firestore`] with [`LocalCache::Persistent`]. This
+/// must be called before any other use of `firestore`, and fails with
+/// [`FirestoreErrorKind::FailedPrecondition`] if another tab already has
+/// persistence enabled.
+pub async fn enable_indexed_db_persistence(firestore: &Firestore) -> Result<(), FirestoreError> {
+    b::enable_indexed_db_persistence(firestore)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Connects to the Firestore emulator at `host` and `port`, such as
+/// `"127.0.0.1"` and `8080`.
+///
+/// Must be called before any other Firestore operation, otherwise it fails.
+pub fn connect_firestore_emulator(
+    firestore: &Firestore,
+    host: &str,
+    port: u16,
+) -> Result<(), FirestoreError> {
+    b::connect_firestore_emulator(firestore, host, port).map_err(Into::into)
+}
+
+/// Stops all network usage. Reads are served from the cache and writes are
+/// queued until [`enable_network`] is called.
+pub async fn disable_network(firestore: &Firestore) -> Result<(), FirestoreError> {
+    b::disable_network(firestore)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Resumes network usage after [`disable_network`].
+pub async fn enable_network(firestore: &Firestore) -> Result<(), FirestoreError> {
+    b::enable_network(firestore)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Resolves once all pending writes have been acknowledged by the server,
+/// such as before going offline or signing out.
+///
+/// Writes made while this is pending are not waited for. This never resolves
+/// while offline.
+pub async fn wait_for_pending_writes(firestore: &Firestore) -> Result<(), FirestoreError> {
+    b::wait_for_pending_writes(firestore)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Returns a [`Query`] over every collection or subcollection with the ID
+/// `collection_id`, regardless of its parent, such as every `"comments"`
+/// subcollection.
+pub fn collection_group(
+    firestore: &Firestore,
+    collection_id: &str,
+) -> Result<Query, FirestoreError> {
+    b::collection_group(firestore, collection_id).map_err(Into::into)
+}
+
+impl DocumentReference {
+    /// Returns the subcollection at `path`, relative to this document, such
+    /// as `"posts"`.
+    pub fn collection(&self, path: &str) -> Result<CollectionReference, FirestoreError> {
+        b::collection_in_doc(self, path).map_err(Into::into)
+    }
+}
+
+impl CollectionReference {
+    /// Returns the document at `id`, relative to this collection. When
+    /// `None`, a unique ID is generated client-side, such as for a document
+    /// that will be created with [`set_doc`].
+    pub fn doc(&self, id: Option<&str>) -> Result<DocumentReference, FirestoreError> {
+        match id {
+            Some(id) => b::doc_in_collection(self, id).map_err(Into::into),
+            None => Ok(b::doc_with_auto_id(self)),
+        }
+    }
+}
+
 impl Transaction {
-    pub async fn get(&self, doc: DocumentReference) -> Result<DocumentSnapshot, FirestoreError> {
+    /// Returns `None` if the document does not exist.
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        doc: &DocumentReference,
+    ) -> Result<Option<T>, FirestoreError> {
+        let snapshot = self.get_snapshot(doc).await?;
+
+        snapshot_data(&snapshot)
+    }
+
+    pub async fn get_snapshot(
+        &self,
+        doc: &DocumentReference,
+    ) -> Result<DocumentSnapshot, FirestoreError> {
         self.get_js(doc)
             .await
             .map_err(|err| err.unchecked_into::<FirebaseError>().into())
             .map(|snapshot| snapshot.unchecked_into())
     }
 
-    pub fn set(&self, doc: DocumentReference, data: JsValue) -> Result<Self, FirestoreError> {
-        self.set_js(doc, data).map_err(Into::into)
+    pub fn set<T: Serialize + ?Sized>(
+        &self,
+        doc: &DocumentReference,
+        data: &T,
+    ) -> Result<Self, FirestoreError> {
+        self.set_js(doc, to_value(data)?).map_err(Into::into)
     }
 
-    pub fn update(&self, doc: DocumentReference, data: JsValue) -> Result<Self, FirestoreError> {
-        self.update_js(doc, data).map_err(Into::into)
+    pub fn update<T: Serialize + ?Sized>(
+        &self,
+        doc: &DocumentReference,
+        data: &T,
+    ) -> Result<Self, FirestoreError> {
+        self.update_js(doc, to_value(data)?).map_err(Into::into)
     }
 
-    pub fn delete(&self, doc: DocumentReference) -> Result<Self, FirestoreError> {
+    pub fn delete(&self, doc: &DocumentReference) -> Result<Self, FirestoreError> {
         self.delete_js(doc).map_err(Into::into)
     }
 }
 
+/// Creates a batch of writes which are committed atomically. Batches are
+/// limited to 500 operations.
+pub fn write_batch(firestore: &Firestore) -> WriteBatch {
+    WriteBatch {
+        batch: b::write_batch(firestore),
+        err: None,
+    }
+}
+
+/// Errors from `set`, `update` and `delete` are deferred until
+/// [`WriteBatch::commit`], so a batch can be assembled in a loop.
+#[derive(Clone, Debug)]
+pub struct WriteBatch {
+    batch: b::WriteBatch,
+    err: Option<FirestoreError>,
+}
+
+impl WriteBatch {
+    pub fn set<T: Serialize + ?Sized>(mut self, doc: &DocumentReference, data: &T) -> Self {
+        if self.err.is_none() {
+            self.err = to_value(data)
+                .and_then(|data| self.batch.set_js(doc, data).map_err(Into::into))
+                .err();
+        }
+
+        self
+    }
+
+    pub fn update<T: Serialize + ?Sized>(mut self, doc: &DocumentReference, data: &T) -> Self {
+        if self.err.is_none() {
+            self.err = to_value(data)
+                .and_then(|data| self.batch.update_js(doc, data).map_err(Into::into))
+                .err();
+        }
+
+        self
+    }
+
+    pub fn delete(mut self, doc: &DocumentReference) -> Self {
+        if self.err.is_none() {
+            self.err = self.batch.delete_js(doc).err().map(Into::into);
+        }
+
+        self
+    }
+
+    /// Commits the batch, or returns the first error which occurred while
+    /// assembling it without writing anything.
+    pub async fn commit(self) -> Result<(), FirestoreError> {
+        if let Some(err) = self.err {
+            return Err(err);
+        }
+
+        self.batch
+            .commit_js()
+            .await
+            .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum TransactionError<Err> {
     #[error("firestore error: {0}")]
@@ -250,8 +1008,13 @@ impl<Err> TransactionError<Err> {
 #[wasm_bindgen]
 pub struct UserAbortedTransaction;
 
+/// Runs `update_fn` in a transaction, committing its writes if it returns
+/// `Ok`, or aborting the transaction if it returns `Err`.
+///
+/// Firestore retries `update_fn` when the documents it read are modified
+/// concurrently, so any side effects in it may run more than once.
 pub async fn run_transaction<F, Fut, T, Err>(
-    firestore: Firestore,
+    firestore: &Firestore,
     update_fn: F,
 ) -> Result<T, TransactionError<Err>>
 where
@@ -266,24 +1029,23 @@ where
 
     let update_fn = Closure::new(clone!([result], move |t| {
         wasm_bindgen_futures::future_to_promise(clone!([update_fn, result], async move {
-            let mut update_fn_borrow = update_fn.borrow_mut();
-            let mut result_borrow = result.borrow_mut();
-
-            match update_fn_borrow(t).await {
-                Ok(v) => {
-                    *result_borrow = Some(Ok(v));
+            let res = update_fn.borrow_mut()(t).await;
+            let aborted = res.is_err();
 
-                    Ok(JsValue::UNDEFINED)
-                }
-                Err(err) => {
-                    *result_borrow = Some(Err(err));
+            // Only the last attempt's result is kept, since Firestore retries
+            // the update function on contention.
+            *result.borrow_mut() = Some(res);
 
-                    Err(UserAbortedTransaction.into())
-                }
+            if aborted {
+                Err(UserAbortedTransaction.into())
+            } else {
+                Ok(JsValue::UNDEFINED)
             }
         }))
     }));
 
+    let mut message = None;
+
     // Check to see if the error is a firebase error
     if let Err(err) = b::run_transaction(firestore, &update_fn).await {
         if let Ok(err) = err.dyn_into::<js_sys::Object>() {
@@ -292,15 +1054,63 @@ where
                     err.unchecked_into::<FirebaseError>().into(),
                 ));
             }
+
+            message = err
+                .dyn_ref::<js_sys::Error>()
+                .map(|err| String::from(err.message()));
         }
     }
 
-    let mut result_borrow = result.borrow_mut();
+    let result = result.borrow_mut().take();
 
-    result_borrow
-        .take()
-        .unwrap()
-        .map_err(|err| TransactionError::User(err))
+    match result {
+        Some(result) => result.map_err(TransactionError::User),
+        // The transaction failed before `update_fn` finished, with an error
+        // that did not come from Firestore.
+        None => Err(TransactionError::Firestore(
+            FirebaseError::new(
+                "unknown",
+                message
+                    .as_deref()
+                    .unwrap_or("transaction failed before the update function finished"),
+            )
+            .into(),
+        )),
+    }
+}
+
+impl GeoPoint {
+    /// Creates a new [`GeoPoint`].
+    ///
+    /// Fails with [`FirestoreErrorKind::InvalidArgument`] if `latitude` is
+    /// not within `[-90, 90]` or `longitude` is not within `[-180, 180]`.
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self, FirestoreError> {
+        Self::new_js(latitude, longitude).map_err(Into::into)
+    }
+}
+
+impl Bytes {
+    /// Fails with [`FirestoreErrorKind::InvalidArgument`] if `base64` is not
+    /// valid base64.
+    pub fn from_base64(base64: &str) -> Result<Self, FirestoreError> {
+        Self::from_base64_js(base64).map_err(Into::into)
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.to_uint8_array().to_vec()
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(bytes: &[u8]) -> Self {
+        Self::from_uint8_array(&js_sys::Uint8Array::from(bytes))
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    fn from(bytes: Bytes) -> Self {
+        bytes.to_vec()
+    }
 }
 
 impl From<SystemTime> for Timestamp {
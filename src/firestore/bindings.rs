@@ -1,5 +1,6 @@
-use crate::FirebaseError;
+use crate::{app::FirebaseApp, FirebaseError};
 use js_sys::Date;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use wasm_bindgen::prelude::*;
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -14,6 +15,7 @@ extern "C" {
     pub type Firestore;
     #[derive(Clone, Debug)]
     pub type DocumentReference;
+    #[wasm_bindgen(extends = Query)]
     #[derive(Clone, Debug)]
     pub type CollectionReference;
     #[derive(Clone, Debug)]
@@ -23,76 +25,251 @@ extern "C" {
     #[derive(Clone, Debug)]
     pub type QuerySnapshot;
     #[derive(Clone, Debug)]
+    pub type DocumentChange;
+    #[derive(Clone, Debug)]
     pub type QueryConstraint;
     #[derive(Clone, Debug)]
+    pub type SnapshotMetadata;
+    #[derive(Clone, Debug)]
     pub type Transaction;
     #[derive(Clone, Debug)]
     pub type Timestamp;
+    #[derive(Clone, Debug)]
+    pub type WriteBatch;
+    #[derive(Clone, Debug)]
+    pub type GeoPoint;
+    #[derive(Clone, Debug)]
+    pub type Bytes;
+    #[derive(Clone, Debug)]
+    pub type FieldPath;
+    #[derive(Clone, Debug)]
+    pub(crate) type AggregateQuerySnapshot;
+    #[derive(Clone, Debug)]
+    pub(crate) type AggregateField;
 
+    /// Returns the [`Firestore`] instance of `app`, or of the default app
+    /// when `None`.
     #[wasm_bindgen(js_name = getFirestore)]
-    pub fn get_firestore() -> Firestore;
+    pub fn get_firestore(app: Option<&FirebaseApp>) -> Firestore;
+
+    #[wasm_bindgen(js_name = initializeFirestore, catch)]
+    pub(crate) fn initialize_firestore(
+        app: &FirebaseApp,
+        settings: &js_sys::Object,
+    ) -> Result<Firestore, FirebaseError>;
+
+    #[wasm_bindgen(js_name = memoryLocalCache)]
+    pub(crate) fn memory_local_cache() -> JsValue;
+
+    #[wasm_bindgen(js_name = persistentLocalCache)]
+    pub(crate) fn persistent_local_cache(settings: &js_sys::Object) -> JsValue;
+
+    #[wasm_bindgen(js_name = persistentSingleTabManager)]
+    pub(crate) fn persistent_single_tab_manager() -> JsValue;
+
+    #[wasm_bindgen(js_name = persistentMultipleTabManager)]
+    pub(crate) fn persistent_multiple_tab_manager() -> JsValue;
+
+    #[wasm_bindgen(js_name = enableIndexedDbPersistence, catch)]
+    pub(crate) async fn enable_indexed_db_persistence(firestore: &Firestore)
+        -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = connectFirestoreEmulator, catch)]
+    pub(crate) fn connect_firestore_emulator(
+        firestore: &Firestore,
+        host: &str,
+        port: u16,
+    ) -> Result<(), FirebaseError>;
+
+    #[wasm_bindgen(js_name = disableNetwork, catch)]
+    pub(crate) async fn disable_network(firestore: &Firestore) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = enableNetwork, catch)]
+    pub(crate) async fn enable_network(firestore: &Firestore) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = waitForPendingWrites, catch)]
+    pub(crate) async fn wait_for_pending_writes(firestore: &Firestore) -> Result<(), JsValue>;
 
     #[wasm_bindgen(catch)]
-    pub fn doc(firestore: Firestore, path: &str) -> Result<DocumentReference, JsValue>;
+    pub fn doc(firestore: &Firestore, path: &str) -> Result<DocumentReference, FirebaseError>;
 
     #[wasm_bindgen(js_name = getDoc, catch)]
-    pub async fn get_doc(doc: DocumentReference) -> Result<JsValue, JsValue>;
+    pub async fn get_doc(doc: &DocumentReference) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getDocFromCache, catch)]
+    pub(crate) async fn get_doc_from_cache(doc: &DocumentReference) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getDocFromServer, catch)]
+    pub(crate) async fn get_doc_from_server(doc: &DocumentReference) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(js_name = getDocs, catch)]
-    pub async fn get_docs(query: Query) -> Result<JsValue, JsValue>;
+    pub async fn get_docs(query: &Query) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getDocsFromCache, catch)]
+    pub(crate) async fn get_docs_from_cache(query: &Query) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getDocsFromServer, catch)]
+    pub(crate) async fn get_docs_from_server(query: &Query) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getCountFromServer, catch)]
+    pub(crate) async fn get_count_from_server(query: &Query) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getAggregateFromServer, catch)]
+    pub(crate) async fn get_aggregate_from_server(
+        query: &Query,
+        aggregate_spec: &js_sys::Object,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen]
+    pub(crate) fn count() -> AggregateField;
+
+    #[wasm_bindgen]
+    pub(crate) fn sum(field: &str) -> AggregateField;
+
+    #[wasm_bindgen]
+    pub(crate) fn average(field: &str) -> AggregateField;
 
     #[wasm_bindgen(js_name = "setDoc", catch)]
-    pub async fn set_doc(doc: DocumentReference, data: JsValue) -> Result<(), JsValue>;
+    pub async fn set_doc(doc: &DocumentReference, data: JsValue) -> Result<(), JsValue>;
 
     #[wasm_bindgen(js_name = "setDoc", catch)]
     pub async fn set_doc_with_options(
-        doc: DocumentReference,
+        doc: &DocumentReference,
         data: JsValue,
         options: SetDocOptions,
     ) -> Result<(), JsValue>;
 
     #[wasm_bindgen(js_name = "updateDoc", catch)]
-    pub async fn update_doc(doc: DocumentReference, data: JsValue) -> Result<(), JsValue>;
+    pub async fn update_doc(doc: &DocumentReference, data: JsValue) -> Result<(), JsValue>;
 
     #[wasm_bindgen(js_name = "addDoc", catch)]
-    pub async fn add_doc(collection: CollectionReference, data: JsValue) -> Result<(), JsValue>;
+    pub async fn add_doc(
+        collection: &CollectionReference,
+        data: JsValue,
+    ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch)]
     pub fn collection(
-        firestore: Firestore,
+        firestore: &Firestore,
+        path: &str,
+    ) -> Result<CollectionReference, FirebaseError>;
+
+    #[wasm_bindgen(js_name = collectionGroup, catch)]
+    pub(crate) fn collection_group(
+        firestore: &Firestore,
+        collection_id: &str,
+    ) -> Result<Query, FirebaseError>;
+
+    #[wasm_bindgen(js_name = collection, catch)]
+    pub(crate) fn collection_in_doc(
+        parent: &DocumentReference,
         path: &str,
     ) -> Result<CollectionReference, FirebaseError>;
 
+    #[wasm_bindgen(js_name = doc, catch)]
+    pub(crate) fn doc_in_collection(
+        parent: &CollectionReference,
+        path: &str,
+    ) -> Result<DocumentReference, FirebaseError>;
+
+    #[wasm_bindgen(js_name = doc)]
+    pub(crate) fn doc_with_auto_id(parent: &CollectionReference) -> DocumentReference;
+
     #[wasm_bindgen(js_name = onSnapshot)]
     pub fn on_snapshot_doc(
-        reference: DocumentReference,
+        reference: &DocumentReference,
         observer: &Closure<dyn FnMut(DocumentSnapshot)>,
     ) -> js_sys::Function;
 
+    #[wasm_bindgen(js_name = onSnapshot)]
+    pub fn on_snapshot_doc_with_error(
+        reference: &DocumentReference,
+        on_next: &Closure<dyn FnMut(DocumentSnapshot)>,
+        on_error: &Closure<dyn FnMut(FirebaseError)>,
+    ) -> js_sys::Function;
+
     #[wasm_bindgen(js_name = onSnapshot)]
     pub fn on_snapshot_query(
-        query: Query,
+        query: &Query,
         observer: &Closure<dyn FnMut(QuerySnapshot)>,
     ) -> js_sys::Function;
 
+    #[wasm_bindgen(js_name = onSnapshot)]
+    pub fn on_snapshot_query_with_error(
+        query: &Query,
+        on_next: &Closure<dyn FnMut(QuerySnapshot)>,
+        on_error: &Closure<dyn FnMut(FirebaseError)>,
+    ) -> js_sys::Function;
+
     #[wasm_bindgen(variadic)]
-    pub fn query(collection: CollectionReference, constraints: Vec<QueryConstraint>) -> Query;
+    pub fn query(query: &Query, constraints: Vec<QueryConstraint>) -> Query;
 
     #[wasm_bindgen(js_name = "where")]
-    pub fn where_(field_path: &str, op_str: &str, value: JsValue) -> QueryConstraint;
+    pub fn where_(field_path: &JsValue, op_str: &str, value: JsValue) -> QueryConstraint;
 
     #[wasm_bindgen(js_name = "orderBy")]
-    pub fn order_by(field_path: &str, direction_str: &str) -> QueryConstraint;
+    pub fn order_by(field_path: &JsValue, direction_str: &str) -> QueryConstraint;
 
     #[wasm_bindgen]
     pub fn limit(number: i32) -> QueryConstraint;
 
+    #[wasm_bindgen(js_name = "limitToLast")]
+    pub fn limit_to_last(number: i32) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = startAt, variadic)]
+    pub(crate) fn start_at(values: Vec<JsValue>) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = startAt)]
+    pub(crate) fn start_at_snapshot(snapshot: &DocumentSnapshot) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = startAfter, variadic)]
+    pub(crate) fn start_after(values: Vec<JsValue>) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = startAfter)]
+    pub(crate) fn start_after_snapshot(snapshot: &DocumentSnapshot) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = endAt, variadic)]
+    pub(crate) fn end_at(values: Vec<JsValue>) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = endAt)]
+    pub(crate) fn end_at_snapshot(snapshot: &DocumentSnapshot) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = endBefore, variadic)]
+    pub(crate) fn end_before(values: Vec<JsValue>) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = endBefore)]
+    pub(crate) fn end_before_snapshot(snapshot: &DocumentSnapshot) -> QueryConstraint;
+
+    #[wasm_bindgen(js_name = documentId)]
+    pub(crate) fn document_id() -> FieldPath;
+
+    /// Whether both references point to the same document in the same
+    /// Firestore instance.
+    #[wasm_bindgen(js_name = refEqual)]
+    pub fn ref_equal(left: &DocumentReference, right: &DocumentReference) -> bool;
+
+    /// Whether both queries, or collections, target the same documents with
+    /// the same constraints.
+    #[wasm_bindgen(js_name = queryEqual)]
+    pub fn query_equal(left: &Query, right: &Query) -> bool;
+
+    /// Whether both snapshots have the same document, data and metadata.
+    #[wasm_bindgen(js_name = snapshotEqual)]
+    pub fn snapshot_equal(left: &DocumentSnapshot, right: &DocumentSnapshot) -> bool;
+
+    /// Whether both snapshots have the same query, documents and metadata.
+    #[wasm_bindgen(js_name = snapshotEqual)]
+    pub fn query_snapshot_equal(left: &QuerySnapshot, right: &QuerySnapshot) -> bool;
+
     #[wasm_bindgen(js_name = deleteDoc, catch)]
-    pub async fn delete_doc(doc: DocumentReference) -> Result<(), JsValue>;
+    pub async fn delete_doc(doc: &DocumentReference) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = writeBatch)]
+    pub fn write_batch(firestore: &Firestore) -> WriteBatch;
 
     #[wasm_bindgen(js_name = runTransaction, catch)]
     pub async fn run_transaction(
-        firestore: Firestore,
+        firestore: &Firestore,
         update_fn: &Closure<dyn FnMut(Transaction) -> js_sys::Promise>,
     ) -> Result<(), JsValue>;
 
@@ -109,6 +286,35 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn docs(this: &QuerySnapshot) -> Vec<DocumentSnapshot>;
 
+    #[wasm_bindgen(method, js_name = docChanges)]
+    pub fn doc_changes(this: &QuerySnapshot) -> Vec<DocumentChange>;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn metadata(this: &QuerySnapshot) -> SnapshotMetadata;
+
+    // =========================================================================
+    //                         AggregateQuerySnapshot
+    // =========================================================================
+
+    #[wasm_bindgen(method)]
+    pub(crate) fn data(this: &AggregateQuerySnapshot) -> js_sys::Object;
+
+    // =========================================================================
+    //                            DocumentChange
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter, js_name = "type")]
+    pub fn type_(this: &DocumentChange) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn doc(this: &DocumentChange) -> DocumentSnapshot;
+
+    #[wasm_bindgen(method, getter, js_name = oldIndex)]
+    pub fn old_index(this: &DocumentChange) -> i32;
+
+    #[wasm_bindgen(method, getter, js_name = newIndex)]
+    pub fn new_index(this: &DocumentChange) -> i32;
+
     // =========================================================================
     //                            DocumentSnapshot
     // =========================================================================
@@ -125,40 +331,90 @@ extern "C" {
     #[wasm_bindgen(method, js_name = "ref")]
     pub fn ref_(this: &DocumentSnapshot) -> DocumentReference;
 
+    #[wasm_bindgen(method, getter)]
+    pub fn metadata(this: &DocumentSnapshot) -> SnapshotMetadata;
+
     // =========================================================================
-    //                            DocumentSnapshot
+    //                            SnapshotMetadata
+    // =========================================================================
+
+    /// Whether the snapshot contains local writes that the server has not
+    /// acknowledged yet, such as to show a "sending" indicator.
+    #[wasm_bindgen(method, getter, js_name = hasPendingWrites)]
+    pub fn has_pending_writes(this: &SnapshotMetadata) -> bool;
+
+    /// Whether the snapshot was read from the local cache rather than the
+    /// server.
+    #[wasm_bindgen(method, getter, js_name = fromCache)]
+    pub fn from_cache(this: &SnapshotMetadata) -> bool;
+
+    #[wasm_bindgen(method, js_name = isEqual)]
+    pub fn is_equal(this: &SnapshotMetadata, other: &SnapshotMetadata) -> bool;
+
+    // =========================================================================
+    //                              Transaction
     // =========================================================================
 
     #[wasm_bindgen(method, catch, js_name = get)]
     pub(crate) async fn get_js(
         this: &Transaction,
-        doc: DocumentReference,
+        doc: &DocumentReference,
     ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(method, js_name = set, catch)]
     pub(crate) fn set_js(
         this: &Transaction,
-        doc: DocumentReference,
+        doc: &DocumentReference,
         data: JsValue,
     ) -> Result<Transaction, FirebaseError>;
 
     #[wasm_bindgen(method, js_name = update, catch)]
     pub(crate) fn update_js(
         this: &Transaction,
-        doc: DocumentReference,
+        doc: &DocumentReference,
         data: JsValue,
     ) -> Result<Transaction, FirebaseError>;
 
     #[wasm_bindgen(method, js_name = delete, catch)]
     pub(crate) fn delete_js(
         this: &Transaction,
-        doc: DocumentReference,
+        doc: &DocumentReference,
     ) -> Result<Transaction, FirebaseError>;
 
+    // =========================================================================
+    //                              WriteBatch
+    // =========================================================================
+
+    #[wasm_bindgen(method, js_name = set, catch)]
+    pub(crate) fn set_js(
+        this: &WriteBatch,
+        doc: &DocumentReference,
+        data: JsValue,
+    ) -> Result<WriteBatch, FirebaseError>;
+
+    #[wasm_bindgen(method, js_name = update, catch)]
+    pub(crate) fn update_js(
+        this: &WriteBatch,
+        doc: &DocumentReference,
+        data: JsValue,
+    ) -> Result<WriteBatch, FirebaseError>;
+
+    #[wasm_bindgen(method, js_name = delete, catch)]
+    pub(crate) fn delete_js(
+        this: &WriteBatch,
+        doc: &DocumentReference,
+    ) -> Result<WriteBatch, FirebaseError>;
+
+    #[wasm_bindgen(method, js_name = commit, catch)]
+    pub(crate) async fn commit_js(this: &WriteBatch) -> Result<(), JsValue>;
+
     // =========================================================================
     //                            Timestamp
     // =========================================================================
 
+    #[wasm_bindgen(constructor)]
+    pub fn new(seconds: f64, nanoseconds: u32) -> Timestamp;
+
     #[wasm_bindgen(js_namespace = Timestamp)]
     pub fn now() -> Timestamp;
 
@@ -177,6 +433,49 @@ extern "C" {
     #[wasm_bindgen(method, js_name = isEqual)]
     pub fn is_equal(this: &Timestamp, other: &Timestamp) -> bool;
 
+    /// Whole seconds since the Unix epoch.
+    #[wasm_bindgen(method, getter)]
+    pub fn seconds(this: &Timestamp) -> f64;
+
+    /// Non-negative fractions of a second, in nanoseconds.
+    #[wasm_bindgen(method, getter)]
+    pub fn nanoseconds(this: &Timestamp) -> u32;
+
+    // =========================================================================
+    //                              GeoPoint
+    // =========================================================================
+
+    #[wasm_bindgen(constructor, catch)]
+    pub(crate) fn new_js(latitude: f64, longitude: f64) -> Result<GeoPoint, FirebaseError>;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn latitude(this: &GeoPoint) -> f64;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn longitude(this: &GeoPoint) -> f64;
+
+    #[wasm_bindgen(method, js_name = isEqual)]
+    pub fn is_equal(this: &GeoPoint, other: &GeoPoint) -> bool;
+
+    // =========================================================================
+    //                                Bytes
+    // =========================================================================
+
+    #[wasm_bindgen(static_method_of = Bytes, js_name = fromUint8Array)]
+    pub(crate) fn from_uint8_array(array: &js_sys::Uint8Array) -> Bytes;
+
+    #[wasm_bindgen(static_method_of = Bytes, js_name = fromBase64String, catch)]
+    pub(crate) fn from_base64_js(base64: &str) -> Result<Bytes, FirebaseError>;
+
+    #[wasm_bindgen(method, js_name = toUint8Array)]
+    pub fn to_uint8_array(this: &Bytes) -> js_sys::Uint8Array;
+
+    #[wasm_bindgen(method, js_name = toBase64)]
+    pub fn to_base64(this: &Bytes) -> String;
+
+    #[wasm_bindgen(method, js_name = isEqual)]
+    pub fn is_equal(this: &Bytes, other: &Bytes) -> bool;
+
     // =========================================================================
     //                            DocumentReference
     // =========================================================================
@@ -186,6 +485,25 @@ extern "C" {
 
     #[wasm_bindgen(method, getter)]
     pub fn id(this: &DocumentReference) -> String;
+
+    /// The collection this document belongs to.
+    #[wasm_bindgen(method, getter)]
+    pub fn parent(this: &DocumentReference) -> CollectionReference;
+
+    // =========================================================================
+    //                           CollectionReference
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter)]
+    pub fn path(this: &CollectionReference) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn id(this: &CollectionReference) -> String;
+
+    /// The document containing this subcollection, or `None` for top-level
+    /// collections.
+    #[wasm_bindgen(method, getter)]
+    pub fn parent(this: &CollectionReference) -> Option<DocumentReference>;
 }
 
 impl PartialEq for Timestamp {
@@ -195,6 +513,94 @@ impl PartialEq for Timestamp {
 }
 impl Eq for Timestamp {}
 
+/// Only serializes with `serde_wasm_bindgen`, which passes the timestamp
+/// through as-is.
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_wasm_bindgen::preserve::serialize(self, serializer)
+    }
+}
+
+/// Lets document fields stored as timestamps deserialize directly into
+/// [`Timestamp`].
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_wasm_bindgen::preserve::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(time: chrono::DateTime<chrono::Utc>) -> Self {
+        Self::new(time.timestamp() as f64, time.timestamp_subsec_nanos())
+    }
+}
+
+/// Returned when a [`Timestamp`] cannot be represented as a
+/// [`chrono::DateTime`].
+#[cfg(feature = "chrono")]
+#[derive(Clone, Copy, Debug, thiserror::Error)]
+#[error("timestamp is out of range")]
+pub struct TimestampOutOfRange;
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = TimestampOutOfRange;
+
+    fn try_from(time: Timestamp) -> Result<Self, Self::Error> {
+        use chrono::TimeZone;
+
+        chrono::Utc
+            .timestamp_opt(time.seconds() as i64, time.nanoseconds())
+            .single()
+            .ok_or(TimestampOutOfRange)
+    }
+}
+
+impl PartialEq for Bytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_equal(other)
+    }
+}
+impl Eq for Bytes {}
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_wasm_bindgen::preserve::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_wasm_bindgen::preserve::deserialize(deserializer)
+    }
+}
+
+impl PartialEq for SnapshotMetadata {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_equal(other)
+    }
+}
+impl Eq for SnapshotMetadata {}
+
+impl PartialEq for GeoPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_equal(other)
+    }
+}
+
+impl Serialize for GeoPoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_wasm_bindgen::preserve::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoPoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_wasm_bindgen::preserve::deserialize(deserializer)
+    }
+}
+
 impl PartialEq for DocumentReference {
     fn eq(&self, other: &Self) -> bool {
         self.path() == other.path()
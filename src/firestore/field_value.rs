@@ -0,0 +1,95 @@
+//! Sentinel values for use in writes, such as [`set_doc`](super::set_doc)
+//! and [`update_doc`](super::update_doc).
+//!
+//! ```no_run
+//! # use firebase_wasm::firestore::{field_value, update_doc, DocumentReference, FirestoreError};
+//! # async fn example(doc: DocumentReference) -> Result<(), FirestoreError> {
+//! #[derive(serde::Serialize)]
+//! struct Counter {
+//!     count: field_value::FieldValue,
+//! }
+//!
+//! update_doc(
+//!     &doc,
+//!     &Counter {
+//!         count: field_value::increment(1.0),
+//!     },
+//! )
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{to_value, FirestoreError};
+use serde::{Serialize, Serializer};
+use wasm_bindgen::prelude::*;
+
+/// Only serializes with `serde_wasm_bindgen`, which passes the sentinel
+/// through as-is.
+impl Serialize for FieldValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_wasm_bindgen::preserve::serialize(self, serializer)
+    }
+}
+
+/// Adds `elements` not already present to an array field.
+pub fn array_union<T: Serialize>(elements: &[T]) -> Result<FieldValue, FirestoreError> {
+    let elements = elements.iter().map(to_value).collect::<Result<_, _>>()?;
+
+    Ok(array_union_js(elements))
+}
+
+/// Removes all instances of `elements` from an array field.
+pub fn array_remove<T: Serialize>(elements: &[T]) -> Result<FieldValue, FirestoreError> {
+    let elements = elements.iter().map(to_value).collect::<Result<_, _>>()?;
+
+    Ok(array_remove_js(elements))
+}
+
+#[wasm_bindgen(module = "firebase/firestore")]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type FieldValue;
+
+    /// Replaced with the server's timestamp when written.
+    #[wasm_bindgen(js_name = serverTimestamp)]
+    pub fn server_timestamp() -> FieldValue;
+
+    /// Increments a numeric field by `n`, or sets it to `n` if it is not a
+    /// number.
+    #[wasm_bindgen]
+    pub fn increment(n: f64) -> FieldValue;
+
+    #[wasm_bindgen(js_name = deleteField)]
+    pub fn delete_field() -> FieldValue;
+
+    #[wasm_bindgen(js_name = arrayUnion, variadic)]
+    fn array_union_js(elements: Vec<JsValue>) -> FieldValue;
+
+    #[wasm_bindgen(js_name = arrayRemove, variadic)]
+    fn array_remove_js(elements: Vec<JsValue>) -> FieldValue;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn increment_is_serialized_as_sentinel() {
+        #[derive(Serialize)]
+        struct Counter {
+            count: FieldValue,
+        }
+
+        let sentinel = increment(1.0);
+
+        let data = to_value(&Counter {
+            count: sentinel.clone(),
+        })
+        .unwrap();
+
+        let count = js_sys::Reflect::get(&data, &"count".into()).unwrap();
+        assert_eq!(count, JsValue::from(sentinel));
+    }
+}
@@ -1,7 +1,119 @@
-use serde::{Deserialize, Serialize};
-use std::{future::Future, marker::PhantomData, pin::Pin};
+use crate::{
+    app::FirebaseApp, FirebaseError, DESERIALIZATION_ERROR_CODE, SERIALIZATION_ERROR_CODE,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{error::Error, fmt, future::Future, marker::PhantomData, pin::Pin, time::Duration};
 use wasm_bindgen::{prelude::*, JsCast};
 
+#[derive(Clone, Debug, derive_more::Deref)]
+pub struct FunctionsError {
+    pub kind: FunctionsErrorKind,
+    #[deref]
+    pub source: FirebaseError,
+}
+
+impl fmt::Display for FunctionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl Error for FunctionsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl FunctionsError {
+    /// Extra information about the error given by the function, such as
+    /// which fields failed validation, or `undefined` if there is none.
+    pub fn details(&self) -> JsValue {
+        js_sys::Reflect::get(&self.source, &"details".into()).unwrap_or(JsValue::UNDEFINED)
+    }
+
+    /// Deserializes [`FunctionsError::details`], returning `None` if there
+    /// are none.
+    pub fn details_as<T: DeserializeOwned>(&self) -> Result<Option<T>, serde_wasm_bindgen::Error> {
+        let details = self.details();
+
+        if details.is_undefined() || details.is_null() {
+            return Ok(None);
+        }
+
+        serde_wasm_bindgen::from_value(details).map(Some)
+    }
+
+    pub(crate) fn serialization(err: serde_wasm_bindgen::Error) -> Self {
+        Self {
+            kind: FunctionsErrorKind::Serialization,
+            source: FirebaseError::from_serde_error(err, SERIALIZATION_ERROR_CODE),
+        }
+    }
+
+    pub(crate) fn deserialization(err: serde_wasm_bindgen::Error) -> Self {
+        Self {
+            kind: FunctionsErrorKind::Deserialization,
+            source: FirebaseError::from_serde_error(err, DESERIALIZATION_ERROR_CODE),
+        }
+    }
+}
+
+impl From<FirebaseError> for FunctionsError {
+    fn from(err: FirebaseError) -> Self {
+        let code = err.code();
+        let kind = code
+            .parse()
+            .unwrap_or_else(|_| FunctionsErrorKind::Other(code));
+
+        Self { kind, source: err }
+    }
+}
+
+#[derive(Clone, Debug, strum_macros::EnumString)]
+#[non_exhaustive]
+pub enum FunctionsErrorKind {
+    #[strum(serialize = "functions/cancelled")]
+    Cancelled,
+    #[strum(serialize = "functions/unknown")]
+    Unknown,
+    #[strum(serialize = "functions/invalid-argument")]
+    InvalidArgument,
+    #[strum(serialize = "functions/deadline-exceeded")]
+    DeadlineExceeded,
+    #[strum(serialize = "functions/not-found")]
+    NotFound,
+    #[strum(serialize = "functions/already-exists")]
+    AlreadyExists,
+    #[strum(serialize = "functions/permission-denied")]
+    PermissionDenied,
+    #[strum(serialize = "functions/resource-exhausted")]
+    ResourceExhausted,
+    #[strum(serialize = "functions/failed-precondition")]
+    FailedPrecondition,
+    #[strum(serialize = "functions/aborted")]
+    Aborted,
+    #[strum(serialize = "functions/out-of-range")]
+    OutOfRange,
+    #[strum(serialize = "functions/unimplemented")]
+    Unimplemented,
+    #[strum(serialize = "functions/internal")]
+    Internal,
+    #[strum(serialize = "functions/unavailable")]
+    Unavailable,
+    #[strum(serialize = "functions/data-loss")]
+    DataLoss,
+    #[strum(serialize = "functions/unauthenticated")]
+    Unauthenticated,
+    /// The request data could not be converted to a JS value.
+    #[strum(serialize = "firebase-wasm/serialization")]
+    Serialization,
+    /// The response data could not be converted from a JS value.
+    #[strum(serialize = "firebase-wasm/deserialization")]
+    Deserialization,
+    #[strum(default)]
+    Other(String),
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 #[builder(field_defaults(default, setter(strip_option)))]
 pub struct HttpsCallableOptions {
@@ -33,13 +145,17 @@ where
         &self,
         data: Req,
     ) -> impl Future<Output = Result<Result<Res, serde_wasm_bindgen::Error>, JsValue>> {
-        let data = serde_wasm_bindgen::to_value(&data).expect("data to serialize successfully");
+        let res = serde_wasm_bindgen::to_value(&data)
+            .map(|data| self.callable.call1(&JsValue::UNDEFINED, &data));
 
-        let res = self.callable.call1(&JsValue::UNDEFINED, &data).unwrap();
+        async move {
+            let res = match res {
+                Ok(res) => res?,
+                Err(err) => return Ok(Err(err)),
+            };
 
-        let fut = wasm_bindgen_futures::JsFuture::from(res.unchecked_into::<js_sys::Promise>());
+            let fut = wasm_bindgen_futures::JsFuture::from(res.unchecked_into::<js_sys::Promise>());
 
-        async move {
             fut.await.map(|res| {
                 serde_wasm_bindgen::from_value::<HttpsCallableResponse<Res>>(res)
                     .map(|res| res.data)
@@ -91,7 +207,15 @@ where
     Req: Serialize,
     Res: for<'de> Deserialize<'de>,
 {
-    let options = serde_wasm_bindgen::to_value(&options).unwrap();
+    let options = options.map_or(JsValue::UNDEFINED, |options| {
+        let obj = js_sys::Object::new();
+
+        if let Some(timeout) = options.timeout {
+            js_sys::Reflect::set(&obj, &"timeout".into(), &(timeout as f64).into()).unwrap();
+        }
+
+        obj.into()
+    });
 
     let callable = https_callable_(functions_instance, name, options);
 
@@ -102,12 +226,122 @@ where
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, TypedBuilder)]
+#[builder(field_defaults(default))]
+pub struct CallableOptions {
+    /// Defaults to 70 seconds.
+    #[builder(setter(strip_option))]
+    pub timeout: Option<Duration>,
+    /// Uses App Check tokens that can only be used once, for functions that
+    /// enforce replay protection.
+    pub limited_use_app_check_tokens: bool,
+}
+
+impl CallableOptions {
+    fn to_value(options: Option<&Self>) -> JsValue {
+        let Some(options) = options else {
+            return JsValue::UNDEFINED;
+        };
+
+        let obj = js_sys::Object::new();
+
+        if let Some(timeout) = options.timeout {
+            let timeout = timeout.as_secs_f64() * 1000.;
+            js_sys::Reflect::set(&obj, &"timeout".into(), &timeout.into()).unwrap();
+        }
+
+        js_sys::Reflect::set(
+            &obj,
+            &"limitedUseAppCheckTokens".into(),
+            &options.limited_use_app_check_tokens.into(),
+        )
+        .unwrap();
+
+        obj.into()
+    }
+}
+
+/// Calls the callable function `name` with `data`, returning the data it
+/// responds with.
+pub async fn call<Req, Res>(
+    functions: &Functions,
+    name: &str,
+    data: &Req,
+    options: Option<&CallableOptions>,
+) -> Result<Res, FunctionsError>
+where
+    Req: Serialize + ?Sized,
+    Res: DeserializeOwned,
+{
+    let callable = https_callable_(functions, name, CallableOptions::to_value(options));
+
+    call_callable(&callable, data).await
+}
+
+/// Like [`call`], for a callable function served at `url`, such as behind a
+/// custom domain.
+pub async fn call_from_url<Req, Res>(
+    functions: &Functions,
+    url: &str,
+    data: &Req,
+    options: Option<&CallableOptions>,
+) -> Result<Res, FunctionsError>
+where
+    Req: Serialize + ?Sized,
+    Res: DeserializeOwned,
+{
+    let callable = https_callable_from_url(functions, url, CallableOptions::to_value(options));
+
+    call_callable(&callable, data).await
+}
+
+async fn call_callable<Req, Res>(
+    callable: &js_sys::Function,
+    data: &Req,
+) -> Result<Res, FunctionsError>
+where
+    Req: Serialize + ?Sized,
+    Res: DeserializeOwned,
+{
+    let data = serde_wasm_bindgen::to_value(data).map_err(FunctionsError::serialization)?;
+
+    let res = callable
+        .call1(&JsValue::UNDEFINED, &data)
+        .map_err(|err| err.unchecked_into::<FirebaseError>())?;
+
+    let res = wasm_bindgen_futures::JsFuture::from(res.unchecked_into::<js_sys::Promise>())
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>())?;
+
+    serde_wasm_bindgen::from_value::<HttpsCallableResponse<Res>>(res)
+        .map(|res| res.data)
+        .map_err(FunctionsError::deserialization)
+}
+
 #[wasm_bindgen(module = "firebase/functions")]
 extern "C" {
+    #[derive(Clone, Debug)]
     pub type Functions;
 
+    /// Returns the [`Functions`] instance of `app`, or of the default app
+    /// when `None`, for functions deployed to `region`, or `"us-central1"`
+    /// when `None`.
     #[wasm_bindgen(js_name = getFunctions)]
-    pub fn get_functions() -> Functions;
+    pub fn get_functions(app: Option<&FirebaseApp>, region: Option<&str>) -> Functions;
+
+    /// Connects to the Functions emulator at `host` and `port`, such as
+    /// `"127.0.0.1"` and `5001`.
+    ///
+    /// Must be called before calling any function.
+    #[wasm_bindgen(js_name = connectFunctionsEmulator)]
+    pub fn connect_functions_emulator(functions: &Functions, host: &str, port: u16);
+
+    #[wasm_bindgen(js_name = httpsCallableFromURL)]
+    fn https_callable_from_url(
+        functions: &Functions,
+        url: &str,
+        options: JsValue,
+    ) -> js_sys::Function;
 
     #[wasm_bindgen(js_name = httpsCallable)]
     fn https_callable_(
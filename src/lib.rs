@@ -22,13 +22,92 @@ extern crate wasm_bindgen_struct;
 
 #[macro_use]
 mod utils;
+pub mod analytics;
+pub mod app;
+pub mod app_check;
 pub mod auth;
+pub mod database;
 pub mod firestore;
 pub mod functions;
+pub mod messaging;
+pub mod performance;
+pub mod remote_config;
 pub mod storage;
+mod stream;
 
-use std::{error::Error, fmt};
-use wasm_bindgen::prelude::*;
+#[cfg(test)]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+use std::{any::Any, error::Error, fmt};
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// Handle to a registered listener, which owns the listener's [`Closure`]s.
+///
+/// The listener is removed when this is dropped, or when
+/// [`Unsubscribe::unsubscribe`] is called.
+#[must_use = "the listener is removed as soon as this is dropped"]
+pub struct Unsubscribe {
+    /// `None` once [`Unsubscribe::unsubscribe`] has been called.
+    unsubscribe: Option<js_sys::Function>,
+    _closures: Box<dyn Any>,
+}
+
+impl Unsubscribe {
+    /// `closures` is kept alive until the listener is removed. Tuples can be
+    /// used for listeners with more than one callback.
+    pub(crate) fn new(unsubscribe: js_sys::Function, closures: impl Any) -> Self {
+        Self {
+            unsubscribe: Some(unsubscribe),
+            _closures: Box::new(closures),
+        }
+    }
+
+    /// Fails if the SDK throws while removing the listener, such as when
+    /// its app was already deleted. Dropping ignores such failures.
+    pub fn unsubscribe(mut self) -> Result<(), JsValue> {
+        match self.unsubscribe.take() {
+            Some(unsubscribe) => unsubscribe.call0(&JsValue::UNDEFINED).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Unsubscribe {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            let _ = unsubscribe.call0(&JsValue::UNDEFINED);
+        }
+    }
+}
+
+/// Error code given to [`FirebaseError`]s created from serde failures.
+pub(crate) const SERIALIZATION_ERROR_CODE: &str = "firebase-wasm/serialization";
+
+/// Error code given to [`FirebaseError`]s created from serde failures when
+/// reading values returned by the SDK.
+pub(crate) const DESERIALIZATION_ERROR_CODE: &str = "firebase-wasm/deserialization";
+
+impl FirebaseError {
+    /// Creates an error for failures detected by this crate rather than the
+    /// SDK.
+    pub(crate) fn new(code: &str, message: &str) -> Self {
+        let err = js_sys::Error::new(message);
+
+        js_sys::Reflect::set(&err, &"code".into(), &code.into()).unwrap();
+
+        err.unchecked_into()
+    }
+
+    /// Wraps a serialization failure in a [`FirebaseError`], so it can be
+    /// surfaced through the same error types as SDK failures.
+    pub(crate) fn from_serde_error(err: serde_wasm_bindgen::Error, code: &str) -> Self {
+        let err = JsValue::from(err);
+
+        js_sys::Reflect::set(&err, &"code".into(), &code.into()).unwrap();
+
+        err.unchecked_into()
+    }
+}
 
 impl fmt::Display for FirebaseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -48,4 +127,7 @@ extern "C" {
 
     #[wasm_bindgen(method, getter)]
     pub fn message(this: &FirebaseError) -> String;
+
+    #[wasm_bindgen(method, getter, js_name = customData)]
+    pub fn custom_data(this: &FirebaseError) -> Option<js_sys::Object>;
 }
@@ -0,0 +1,187 @@
+use crate::{app::FirebaseApp, stream::callback_stream, FirebaseError};
+use futures::Stream;
+use std::{collections::HashMap, error::Error, fmt};
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[derive(Clone, Debug, derive_more::Deref)]
+pub struct MessagingError {
+    pub kind: MessagingErrorKind,
+    #[deref]
+    pub source: FirebaseError,
+}
+
+impl fmt::Display for MessagingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl Error for MessagingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<FirebaseError> for MessagingError {
+    fn from(err: FirebaseError) -> Self {
+        let code = err.code();
+        let kind = code
+            .parse()
+            .unwrap_or_else(|_| MessagingErrorKind::Other(code));
+
+        Self { kind, source: err }
+    }
+}
+
+#[derive(Clone, Debug, strum_macros::EnumString)]
+#[non_exhaustive]
+pub enum MessagingErrorKind {
+    /// The user denied the notification permission.
+    #[strum(serialize = "messaging/permission-blocked")]
+    PermissionBlocked,
+    /// The user dismissed the notification permission prompt.
+    #[strum(serialize = "messaging/permission-default")]
+    PermissionDefault,
+    #[strum(serialize = "messaging/unsupported-browser")]
+    UnsupportedBrowser,
+    #[strum(serialize = "messaging/failed-service-worker-registration")]
+    FailedServiceWorkerRegistration,
+    #[strum(serialize = "messaging/token-subscribe-failed")]
+    TokenSubscribeFailed,
+    #[strum(serialize = "messaging/token-unsubscribe-failed")]
+    TokenUnsubscribeFailed,
+    #[strum(default)]
+    Other(String),
+}
+
+/// Returns the registration token that push messages are sent to, asking
+/// the user for the notification permission if needed.
+///
+/// `vapid_key` is the public key of the web push certificate from the
+/// Firebase console. Returns `None` if the user does not grant the
+/// permission.
+pub async fn get_token(
+    messaging: &Messaging,
+    vapid_key: &str,
+) -> Result<Option<String>, MessagingError> {
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &"vapidKey".into(), &vapid_key.into()).unwrap();
+
+    match get_token_js(messaging, options.into()).await {
+        Ok(token) => Ok(token.as_string().filter(|token| !token.is_empty())),
+        Err(err) => {
+            let err = MessagingError::from(err.unchecked_into::<FirebaseError>());
+
+            match err.kind {
+                MessagingErrorKind::PermissionBlocked | MessagingErrorKind::PermissionDefault => {
+                    Ok(None)
+                }
+                _ => Err(err),
+            }
+        }
+    }
+}
+
+/// Deletes the registration token, such as when the user signs out, so
+/// that no more messages are received until [`get_token`] is called again.
+pub async fn delete_token(messaging: &Messaging) -> Result<(), MessagingError> {
+    delete_token_js(messaging)
+        .await
+        .map(|_| ())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Stream of messages received while the app is in the foreground, which
+/// stops listening when dropped.
+///
+/// No system notification is shown for these messages, so the app has to
+/// display them itself.
+pub fn on_message_stream(messaging: &Messaging) -> impl Stream<Item = MessagePayload> {
+    callback_stream(|next| on_message(messaging, next), |payload| payload)
+}
+
+impl MessagePayload {
+    /// The message's custom key-value pairs.
+    pub fn data(&self) -> HashMap<String, String> {
+        self.data_js()
+            .and_then(|data| serde_wasm_bindgen::from_value(data.into()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Whether the browser supports the APIs messaging needs, such as service
+/// workers and the Push API.
+pub async fn is_supported() -> bool {
+    is_supported_js()
+        .await
+        .ok()
+        .and_then(|supported| supported.as_bool())
+        .unwrap_or_default()
+}
+
+#[wasm_bindgen(module = "firebase/messaging")]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type Messaging;
+    #[derive(Clone, Debug)]
+    pub type MessagePayload;
+    #[derive(Clone, Debug)]
+    pub type NotificationPayload;
+
+    /// Returns the [`Messaging`] instance of `app`, or of the default app
+    /// when `None`.
+    ///
+    /// Throws if messaging is not supported, which can be checked with
+    /// [`is_supported`].
+    #[wasm_bindgen(js_name = getMessaging)]
+    pub fn get_messaging(app: Option<&FirebaseApp>) -> Messaging;
+
+    #[wasm_bindgen(js_name = onMessage)]
+    fn on_message(
+        messaging: &Messaging,
+        next: &Closure<dyn FnMut(MessagePayload)>,
+    ) -> js_sys::Function;
+
+    #[wasm_bindgen(js_name = deleteToken, catch)]
+    async fn delete_token_js(messaging: &Messaging) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getToken, catch)]
+    async fn get_token_js(messaging: &Messaging, options: JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = isSupported, catch)]
+    async fn is_supported_js() -> Result<JsValue, JsValue>;
+
+    // =========================================================================
+    //                             MessagePayload
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter)]
+    pub fn notification(this: &MessagePayload) -> Option<NotificationPayload>;
+
+    #[wasm_bindgen(method, getter, js_name = data)]
+    fn data_js(this: &MessagePayload) -> Option<js_sys::Object>;
+
+    /// The sender of the message.
+    #[wasm_bindgen(method, getter)]
+    pub fn from(this: &MessagePayload) -> String;
+
+    #[wasm_bindgen(method, getter, js_name = messageId)]
+    pub fn message_id(this: &MessagePayload) -> String;
+
+    #[wasm_bindgen(method, getter, js_name = collapseKey)]
+    pub fn collapse_key(this: &MessagePayload) -> String;
+
+    // =========================================================================
+    //                           NotificationPayload
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter)]
+    pub fn title(this: &NotificationPayload) -> Option<String>;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn body(this: &NotificationPayload) -> Option<String>;
+
+    /// The URL of an image to show in the notification.
+    #[wasm_bindgen(method, getter)]
+    pub fn image(this: &NotificationPayload) -> Option<String>;
+}
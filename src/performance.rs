@@ -0,0 +1,71 @@
+use crate::app::FirebaseApp;
+use std::ops::Deref;
+use wasm_bindgen::prelude::*;
+
+/// Runs a [`Trace`] until dropped, for timing a scope.
+#[derive(Debug)]
+pub struct TraceGuard {
+    trace: Trace,
+}
+
+impl Deref for TraceGuard {
+    type Target = Trace;
+
+    fn deref(&self) -> &Self::Target {
+        &self.trace
+    }
+}
+
+impl Drop for TraceGuard {
+    fn drop(&mut self) {
+        self.trace.stop();
+    }
+}
+
+impl Trace {
+    /// Starts the trace, stopping it when the returned guard is dropped.
+    pub fn start_guard(self) -> TraceGuard {
+        self.start();
+
+        TraceGuard { trace: self }
+    }
+}
+
+#[wasm_bindgen(module = "firebase/performance")]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type Performance;
+    #[derive(Clone, Debug)]
+    #[wasm_bindgen(js_name = PerformanceTrace)]
+    pub type Trace;
+
+    /// Returns the [`Performance`] instance of `app`, or of the default app
+    /// when `None`, which starts collecting automatic traces.
+    #[wasm_bindgen(js_name = getPerformance)]
+    pub fn get_performance(app: Option<&FirebaseApp>) -> Performance;
+
+    /// Creates a custom trace named `name`, such as `"render_dashboard"`,
+    /// which is recorded once stopped.
+    pub fn trace(performance: &Performance, name: &str) -> Trace;
+
+    // =========================================================================
+    //                                 Trace
+    // =========================================================================
+
+    #[wasm_bindgen(method)]
+    pub fn start(this: &Trace);
+
+    #[wasm_bindgen(method)]
+    pub fn stop(this: &Trace);
+
+    /// Sets `name` to `value`, which is floored to an integer.
+    #[wasm_bindgen(method, js_name = putMetric)]
+    pub fn put_metric(this: &Trace, name: &str, value: f64);
+
+    /// Adds `delta` to `name`, starting from 0 if it is not set.
+    #[wasm_bindgen(method, js_name = incrementMetric)]
+    pub fn increment_metric(this: &Trace, name: &str, delta: f64);
+
+    #[wasm_bindgen(method, js_name = putAttribute)]
+    pub fn put_attribute(this: &Trace, name: &str, value: &str);
+}
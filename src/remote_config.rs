@@ -0,0 +1,241 @@
+use crate::{app::FirebaseApp, FirebaseError, SERIALIZATION_ERROR_CODE};
+use serde::Serialize;
+use std::{collections::HashMap, error::Error, fmt};
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[derive(Clone, Debug, derive_more::Deref)]
+pub struct RemoteConfigError {
+    pub kind: RemoteConfigErrorKind,
+    #[deref]
+    pub source: FirebaseError,
+}
+
+impl fmt::Display for RemoteConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl Error for RemoteConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl RemoteConfigError {
+    pub(crate) fn serialization(err: serde_wasm_bindgen::Error) -> Self {
+        Self {
+            kind: RemoteConfigErrorKind::Serialization,
+            source: FirebaseError::from_serde_error(err, SERIALIZATION_ERROR_CODE),
+        }
+    }
+}
+
+impl From<FirebaseError> for RemoteConfigError {
+    fn from(err: FirebaseError) -> Self {
+        let code = err.code();
+        let kind = code
+            .parse()
+            .unwrap_or_else(|_| RemoteConfigErrorKind::Other(code));
+
+        Self { kind, source: err }
+    }
+}
+
+#[derive(Clone, Debug, strum_macros::EnumString)]
+#[non_exhaustive]
+pub enum RemoteConfigErrorKind {
+    /// Fetched too often. Lower the minimum fetch interval during
+    /// development.
+    #[strum(serialize = "remoteconfig/fetch-throttle")]
+    FetchThrottle,
+    #[strum(serialize = "remoteconfig/fetch-timeout")]
+    FetchTimeout,
+    #[strum(serialize = "remoteconfig/fetch-client-network")]
+    FetchClientNetwork,
+    #[strum(serialize = "remoteconfig/fetch-status")]
+    FetchStatus,
+    #[strum(serialize = "remoteconfig/fetch-client-parse")]
+    FetchClientParse,
+    #[strum(serialize = "remoteconfig/indexed-db-unavailable")]
+    IndexedDbUnavailable,
+    /// Defaults could not be converted to a JS value.
+    #[strum(serialize = "firebase-wasm/serialization")]
+    Serialization,
+    #[strum(default)]
+    Other(String),
+}
+
+/// Where a [`Value`] came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum_macros::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum ValueSource {
+    /// There is no default or remote value for the key, so the value is a
+    /// static fallback, such as an empty string.
+    Static,
+    /// The value was set with [`set_defaults`].
+    Default,
+    /// The value was fetched from the server.
+    Remote,
+}
+
+impl Value {
+    /// Returns `None` for a source added in a newer SDK.
+    pub fn source(&self) -> Option<ValueSource> {
+        self.get_source().parse().ok()
+    }
+}
+
+/// Fetches the latest config and activates it, making its values available
+/// to the getters.
+///
+/// Returns `true` if the activated config changed.
+pub async fn fetch_and_activate(remote_config: &RemoteConfig) -> Result<bool, RemoteConfigError> {
+    fetch_and_activate_js(remote_config)
+        .await
+        .map(|changed| changed.as_bool().unwrap_or_default())
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Sets the values used until a config has been fetched and activated, and
+/// for keys that are missing from it. `defaults` should serialize to an
+/// object.
+///
+/// Call this right after [`get_remote_config`], so the defaults apply
+/// before the first fetch completes.
+pub fn set_defaults<T: Serialize + ?Sized>(
+    remote_config: &RemoteConfig,
+    defaults: &T,
+) -> Result<(), RemoteConfigError> {
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+
+    let defaults = defaults
+        .serialize(&serializer)
+        .map_err(RemoteConfigError::serialization)?;
+
+    remote_config.set_default_config(defaults);
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RemoteConfigSettings {
+    /// How long a fetched config is cached before fetching again. Defaults
+    /// to 12 hours, which should be lowered during development to avoid
+    /// being throttled.
+    pub minimum_fetch_interval_millis: u32,
+    /// Defaults to 60 seconds.
+    pub fetch_timeout_millis: u32,
+}
+
+impl Default for RemoteConfigSettings {
+    fn default() -> Self {
+        Self {
+            minimum_fetch_interval_millis: 43_200_000,
+            fetch_timeout_millis: 60_000,
+        }
+    }
+}
+
+pub fn set_settings(remote_config: &RemoteConfig, settings: RemoteConfigSettings) {
+    let obj = js_sys::Object::new();
+
+    js_sys::Reflect::set(
+        &obj,
+        &"minimumFetchIntervalMillis".into(),
+        &settings.minimum_fetch_interval_millis.into(),
+    )
+    .unwrap();
+    js_sys::Reflect::set(
+        &obj,
+        &"fetchTimeoutMillis".into(),
+        &settings.fetch_timeout_millis.into(),
+    )
+    .unwrap();
+
+    remote_config.set_settings_js(obj.into());
+}
+
+/// Resolves once the last activated config has been loaded from storage,
+/// so the getters return it rather than the defaults.
+pub async fn ensure_initialized(remote_config: &RemoteConfig) -> Result<(), RemoteConfigError> {
+    ensure_initialized_js(remote_config)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+/// Returns every parameter, such as for a debug screen.
+pub fn get_all(remote_config: &RemoteConfig) -> HashMap<String, Value> {
+    js_sys::Object::entries(&get_all_js(remote_config))
+        .iter()
+        .filter_map(|entry| {
+            let entry = entry.unchecked_into::<js_sys::Array>();
+
+            Some((
+                entry.get(0).as_string()?,
+                entry.get(1).unchecked_into::<Value>(),
+            ))
+        })
+        .collect()
+}
+
+#[wasm_bindgen(module = "firebase/remote-config")]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type RemoteConfig;
+    #[derive(Clone, Debug)]
+    pub type Value;
+
+    /// Returns the [`RemoteConfig`] instance of `app`, or of the default app
+    /// when `None`.
+    #[wasm_bindgen(js_name = getRemoteConfig)]
+    pub fn get_remote_config(app: Option<&FirebaseApp>) -> RemoteConfig;
+
+    #[wasm_bindgen(js_name = fetchAndActivate, catch)]
+    async fn fetch_and_activate_js(remote_config: &RemoteConfig) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = ensureInitialized, catch)]
+    async fn ensure_initialized_js(remote_config: &RemoteConfig) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = getAll)]
+    fn get_all_js(remote_config: &RemoteConfig) -> js_sys::Object;
+
+    #[wasm_bindgen(js_name = getString)]
+    pub fn get_string(remote_config: &RemoteConfig, key: &str) -> String;
+
+    #[wasm_bindgen(js_name = getNumber)]
+    pub fn get_number(remote_config: &RemoteConfig, key: &str) -> f64;
+
+    #[wasm_bindgen(js_name = getBoolean)]
+    pub fn get_boolean(remote_config: &RemoteConfig, key: &str) -> bool;
+
+    #[wasm_bindgen(js_name = getValue)]
+    pub fn get_value(remote_config: &RemoteConfig, key: &str) -> Value;
+
+    // =========================================================================
+    //                              RemoteConfig
+    // =========================================================================
+
+    #[wasm_bindgen(method, setter, js_name = defaultConfig)]
+    fn set_default_config(this: &RemoteConfig, default_config: JsValue);
+
+    #[wasm_bindgen(method, setter, js_name = settings)]
+    fn set_settings_js(this: &RemoteConfig, settings: JsValue);
+
+    // =========================================================================
+    //                                 Value
+    // =========================================================================
+
+    #[wasm_bindgen(method, js_name = asString)]
+    pub fn as_string(this: &Value) -> String;
+
+    #[wasm_bindgen(method, js_name = asNumber)]
+    pub fn as_number(this: &Value) -> f64;
+
+    /// `true` for values such as `"true"`, `"1"` and `"on"`.
+    #[wasm_bindgen(method, js_name = asBoolean)]
+    pub fn as_boolean(this: &Value) -> bool;
+
+    #[wasm_bindgen(method, js_name = getSource)]
+    fn get_source(this: &Value) -> String;
+}
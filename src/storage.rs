@@ -1,114 +1,478 @@
 mod bindings;
 
+use crate::{
+    stream::CallbackStream, FirebaseError, Unsubscribe, DESERIALIZATION_ERROR_CODE,
+    SERIALIZATION_ERROR_CODE,
+};
+use bindings as b;
 pub use bindings::{
-    delete_object, get_download_url, get_storage, ref_, upload_bytes, FullMetadata, Ref,
-    SettableMetadata, Storage, UploadMetadata, UploadMetadataOptions, UploadTask,
-    UploadTaskSnapshot,
+    connect_storage_emulator, get_storage, storage_ref, FirebaseStorage, FullMetadata, ListResult,
+    SettableMetadata, SettableMetadataOptions, StorageReference, UploadMetadata,
+    UploadMetadataOptions, UploadResult, UploadTaskSnapshot,
 };
-use futures::Stream;
+use futures::{channel::mpsc, Stream, StreamExt};
+use serde::Serialize;
 use std::{
-    cell::RefCell,
+    error::Error,
+    fmt,
     pin::Pin,
-    rc::Rc,
-    task::{Context, Poll, Waker},
+    task::{Context, Poll},
 };
 use wasm_bindgen::{prelude::*, JsCast};
 
-impl UploadTask {
-    pub fn async_iter(&self) -> UploadTaskAsyncIter {
-        let waker: Rc<RefCell<Option<Waker>>> = Rc::default();
-        let completed: Rc<RefCell<bool>> = Rc::default();
-        let snapshot: Rc<RefCell<Option<UploadTaskSnapshot>>> = Rc::default();
-        let err: Rc<RefCell<Option<JsValue>>> = Rc::default();
-
-        let on_snapshot = Closure::new(clone!([snapshot, waker], move |js_snapshot| {
-            *snapshot.borrow_mut() = Some(js_snapshot);
-
-            if let Some(w) = waker.borrow().as_ref() {
-                w.wake_by_ref();
-            }
-        }));
-        let on_err = Closure::new(clone!([completed, err, waker], move |js_err| {
-            *err.borrow_mut() = Some(js_err);
+#[deprecated = "renamed to `FirebaseStorage`"]
+pub type Storage = FirebaseStorage;
 
-            // Complete the stream since we errored
-            *completed.borrow_mut() = true;
+#[deprecated = "renamed to `StorageReference`"]
+pub type Ref = StorageReference;
 
-            if let Some(w) = waker.borrow().as_ref() {
-                w.wake_by_ref()
-            }
-        }));
-        let on_complete = Closure::new(clone!([completed, waker], move || {
-            *completed.borrow_mut() = true;
+#[derive(Clone, Debug, derive_more::Deref)]
+pub struct StorageError {
+    pub kind: StorageErrorKind,
+    #[deref]
+    pub source: FirebaseError,
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl Error for StorageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl StorageError {
+    pub(crate) fn serialization(err: serde_wasm_bindgen::Error) -> Self {
+        Self {
+            kind: StorageErrorKind::Serialization,
+            source: FirebaseError::from_serde_error(err, SERIALIZATION_ERROR_CODE),
+        }
+    }
 
-            // Notify waker
-            let waker_borrow = waker.borrow();
+    pub(crate) fn deserialization(err: serde_wasm_bindgen::Error) -> Self {
+        Self {
+            kind: StorageErrorKind::Deserialization,
+            source: FirebaseError::from_serde_error(err, DESERIALIZATION_ERROR_CODE),
+        }
+    }
+}
+
+impl From<FirebaseError> for StorageError {
+    fn from(err: FirebaseError) -> Self {
+        let code = err.code();
+        let kind = code
+            .parse()
+            .unwrap_or_else(|_| StorageErrorKind::Other(code));
+
+        Self { kind, source: err }
+    }
+}
+
+#[derive(Clone, Debug, strum_macros::EnumString)]
+#[non_exhaustive]
+pub enum StorageErrorKind {
+    #[strum(serialize = "storage/unknown")]
+    Unknown,
+    #[strum(serialize = "storage/object-not-found")]
+    ObjectNotFound,
+    #[strum(serialize = "storage/bucket-not-found")]
+    BucketNotFound,
+    #[strum(serialize = "storage/project-not-found")]
+    ProjectNotFound,
+    #[strum(serialize = "storage/quota-exceeded")]
+    QuotaExceeded,
+    /// The user is not signed in, and the security rules require it.
+    #[strum(serialize = "storage/unauthenticated")]
+    Unauthenticated,
+    /// The security rules deny the operation.
+    #[strum(serialize = "storage/unauthorized")]
+    Unauthorized,
+    #[strum(serialize = "storage/retry-limit-exceeded")]
+    RetryLimitExceeded,
+    #[strum(serialize = "storage/invalid-checksum")]
+    InvalidChecksum,
+    /// The upload was canceled with [`UploadTask::cancel`].
+    #[strum(serialize = "storage/canceled")]
+    Canceled,
+    #[strum(serialize = "storage/invalid-url")]
+    InvalidUrl,
+    #[strum(serialize = "storage/invalid-argument")]
+    InvalidArgument,
+    #[strum(serialize = "storage/no-default-bucket")]
+    NoDefaultBucket,
+    #[strum(serialize = "storage/cannot-slice-blob")]
+    CannotSliceBlob,
+    #[strum(serialize = "storage/server-file-wrong-size")]
+    ServerFileWrongSize,
+    /// Metadata could not be converted to a JS value.
+    #[strum(serialize = "firebase-wasm/serialization")]
+    Serialization,
+    /// Data returned by Storage could not be converted from a JS value.
+    #[strum(serialize = "firebase-wasm/deserialization")]
+    Deserialization,
+    #[strum(default)]
+    Other(String),
+}
+
+fn metadata_to_value(metadata: Option<&UploadMetadataOptions>) -> Result<JsValue, StorageError> {
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+
+    metadata
+        .serialize(&serializer)
+        .map_err(StorageError::serialization)
+}
+
+impl StorageReference {
+    /// Returns the reference at the relative `path`, such as
+    /// `"thumbnails/cat.png"`.
+    pub fn child(&self, path: &str) -> StorageReference {
+        b::child_ref(self, path)
+    }
+}
+
+/// Uploads `data` to `ref_` in a single request, replacing any existing
+/// object.
+///
+/// For resumable uploads see [`upload_blob_resumable`].
+pub async fn upload_bytes(
+    ref_: &StorageReference,
+    data: &[u8],
+    metadata: Option<&UploadMetadataOptions>,
+) -> Result<UploadResult, StorageError> {
+    let metadata = metadata_to_value(metadata)?;
+
+    b::upload_bytes(ref_, data, metadata)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+        .map(|result| result.unchecked_into())
+}
+
+/// Like [`upload_bytes`], for a [`web_sys::Blob`] or `File`,
+/// such as from an `<input type="file">`.
+///
+/// The blob is handed to the SDK as-is, without being copied into wasm
+/// memory, so prefer this for large files.
+pub async fn upload_blob(
+    ref_: &StorageReference,
+    data: &web_sys::Blob,
+    metadata: Option<&UploadMetadataOptions>,
+) -> Result<UploadResult, StorageError> {
+    let metadata = metadata_to_value(metadata)?;
+
+    b::upload_blob(ref_, data, metadata)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+        .map(|result| result.unchecked_into())
+}
+
+/// How [`upload_string`] decodes its value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StringFormat {
+    /// The string's UTF-8 bytes.
+    #[default]
+    Raw,
+    Base64,
+    /// Base64 with the URL-safe alphabet.
+    Base64Url,
+    /// A `data:` URL, such as from `HTMLCanvasElement.toDataURL()`. Its MIME
+    /// type is used as the content type unless the metadata sets one.
+    DataUrl,
+}
+
+/// Uploads the bytes encoded in `value` to `ref_`, replacing any existing
+/// object.
+pub async fn upload_string(
+    ref_: &StorageReference,
+    value: &str,
+    format: StringFormat,
+    metadata: Option<&UploadMetadataOptions>,
+) -> Result<UploadResult, StorageError> {
+    let metadata = metadata_to_value(metadata)?;
+
+    let format = match format {
+        StringFormat::Raw => "raw",
+        StringFormat::Base64 => "base64",
+        StringFormat::Base64Url => "base64url",
+        StringFormat::DataUrl => "data_url",
+    };
+
+    b::upload_string(ref_, value, format, metadata)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+        .map(|result| result.unchecked_into())
+}
+
+/// Downloads the object's contents.
+///
+/// Fails if the object is larger than `max_bytes`, which guards against
+/// buffering unexpectedly large files.
+pub async fn get_bytes(
+    ref_: &StorageReference,
+    max_bytes: Option<u64>,
+) -> Result<Vec<u8>, StorageError> {
+    b::get_bytes(ref_, max_bytes.map(|max_bytes| max_bytes as f64))
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+        .map(|buffer| js_sys::Uint8Array::new(&buffer).to_vec())
+}
+
+/// Like [`get_bytes`], keeping the contents in JS memory.
+pub async fn get_blob(
+    ref_: &StorageReference,
+    max_bytes: Option<u64>,
+) -> Result<web_sys::Blob, StorageError> {
+    b::get_blob(ref_, max_bytes.map(|max_bytes| max_bytes as f64))
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+        .map(|blob| blob.unchecked_into())
+}
+
+/// Deletes the object. Fails with [`StorageErrorKind::ObjectNotFound`] if it
+/// does not exist.
+pub async fn delete_object(ref_: &StorageReference) -> Result<(), StorageError> {
+    b::delete_object(ref_)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Default, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(strip_option)))]
+pub struct ListOptions {
+    /// At most 1000, which is also the default.
+    pub max_results: Option<u32>,
+    /// The [`ListResult::next_page_token`] of the previous page.
+    #[builder(setter(strip_option, into))]
+    pub page_token: Option<String>,
+}
 
-            if let Some(w) = waker_borrow.as_ref() {
-                w.wake_by_ref();
-            }
+impl ListResult {
+    /// The objects directly under the listed reference.
+    pub fn items(&self) -> Vec<StorageReference> {
+        self.items_js()
+            .iter()
+            .map(JsCast::unchecked_into::<StorageReference>)
+            .collect()
+    }
+
+    /// The "folders" directly under the listed reference, which contain
+    /// further objects.
+    pub fn prefixes(&self) -> Vec<StorageReference> {
+        self.prefixes_js()
+            .iter()
+            .map(JsCast::unchecked_into::<StorageReference>)
+            .collect()
+    }
+}
+
+/// Lists every object and prefix under `ref_`, fetching as many pages as
+/// needed.
+///
+/// Prefer [`list`] for prefixes with many objects.
+pub async fn list_all(ref_: &StorageReference) -> Result<ListResult, StorageError> {
+    b::list_all(ref_)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+        .map(|result| result.unchecked_into())
+}
+
+/// Lists one page of objects and prefixes under `ref_`.
+pub async fn list(
+    ref_: &StorageReference,
+    options: &ListOptions,
+) -> Result<ListResult, StorageError> {
+    let options = serde_wasm_bindgen::to_value(options).map_err(StorageError::serialization)?;
+
+    b::list(ref_, options)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+        .map(|result| result.unchecked_into())
+}
+
+/// Returns a long-lived URL that can be used to download the object, such
+/// as in an `<img>`.
+pub async fn get_download_url(ref_: &StorageReference) -> Result<String, StorageError> {
+    let url = b::get_download_url(ref_)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>())?;
+
+    serde_wasm_bindgen::from_value(url).map_err(StorageError::deserialization)
+}
+
+/// The state of an [`UploadTask`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum_macros::EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[non_exhaustive]
+pub enum TaskState {
+    Running,
+    Paused,
+    Success,
+    Canceled,
+    Error,
+    /// A state added in a newer SDK.
+    #[strum(disabled)]
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UploadProgress {
+    pub bytes_transferred: u64,
+    pub total_bytes: u64,
+    pub state: TaskState,
+}
+
+impl From<&UploadTaskSnapshot> for UploadProgress {
+    fn from(snapshot: &UploadTaskSnapshot) -> Self {
+        Self {
+            bytes_transferred: snapshot.bytes_transferred() as u64,
+            total_bytes: snapshot.total_bytes() as u64,
+            state: snapshot.state(),
+        }
+    }
+}
+
+impl UploadTaskSnapshot {
+    pub fn state(&self) -> TaskState {
+        self.state_js().parse().unwrap_or(TaskState::Unknown)
+    }
+}
+
+/// A resumable upload, which is a [`Stream`] of its progress.
+///
+/// The stream ends when the upload completes, fails or is canceled, after
+/// which [`UploadTask::complete`] gives the outcome. Dropping the task stops
+/// listening for progress, but does not cancel the upload.
+pub struct UploadTask {
+    task: b::UploadTask,
+    progress: CallbackStream<UploadProgress>,
+}
+
+impl UploadTask {
+    fn new(task: b::UploadTask) -> Self {
+        let (tx, rx) = mpsc::unbounded();
+
+        let on_snapshot = Closure::new(clone!([tx], move |snapshot: UploadTaskSnapshot| {
+            tx.unbounded_send(UploadProgress::from(&snapshot)).ok();
         }));
+        let on_err = Closure::new(clone!([tx], move |_: JsValue| {
+            tx.close_channel();
+        }));
+        let on_complete = Closure::new(move || {
+            tx.close_channel();
+        });
 
-        let unsub = self.on(
+        let unsubscribe = task.on(
             "state_changed",
             &on_snapshot,
             Some(&on_err),
             Some(&on_complete),
         );
 
-        UploadTaskAsyncIter {
-            _on_snapshot: on_snapshot,
-            _on_err: on_err,
-            _on_complete: on_complete,
-            snapshot,
-            err,
-            completed,
-            waker,
-            unsub,
+        Self {
+            task,
+            progress: CallbackStream::new(
+                rx,
+                Unsubscribe::new(unsubscribe, (on_snapshot, on_err, on_complete)),
+            ),
         }
     }
-}
 
-pub struct UploadTaskAsyncIter {
-    _on_snapshot: Closure<dyn FnMut(UploadTaskSnapshot)>,
-    _on_err: Closure<dyn FnMut(JsValue)>,
-    _on_complete: Closure<dyn FnMut()>,
-    snapshot: Rc<RefCell<Option<UploadTaskSnapshot>>>,
-    err: Rc<RefCell<Option<JsValue>>>,
-    completed: Rc<RefCell<bool>>,
-    waker: Rc<RefCell<Option<Waker>>>,
-    unsub: js_sys::Function,
+    /// The latest progress of the upload.
+    pub fn progress(&self) -> UploadProgress {
+        UploadProgress::from(&self.task.snapshot())
+    }
+
+    /// Returns `false` if the upload could not be paused, such as when it
+    /// already finished.
+    pub fn pause(&self) -> bool {
+        self.task.pause_js()
+    }
+
+    /// Resumes a paused upload. Returns `false` if it was not paused.
+    pub fn resume(&self) -> bool {
+        self.task.resume_js()
+    }
+
+    /// Cancels the upload, after which [`UploadTask::complete`] fails with
+    /// [`StorageErrorKind::Canceled`]. Returns `false` if the upload already
+    /// finished.
+    pub fn cancel(&self) -> bool {
+        self.task.cancel_js()
+    }
+
+    /// Waits for the upload to finish.
+    pub async fn complete(self) -> Result<UploadResult, StorageError> {
+        let promise = js_sys::Promise::resolve(&self.task);
+
+        wasm_bindgen_futures::JsFuture::from(promise)
+            .await
+            .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+            .map(|snapshot| snapshot.unchecked_into())
+    }
 }
 
-impl Drop for UploadTaskAsyncIter {
-    fn drop(&mut self) {
-        self.unsub.call0(&JsValue::UNDEFINED).unwrap();
+impl Stream for UploadTask {
+    type Item = UploadProgress;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.progress.poll_next_unpin(cx)
     }
 }
 
-impl Stream for UploadTaskAsyncIter {
-    type Item = Result<UploadTaskSnapshot, JsValue>;
+/// Uploads `data` to `ref_`, reporting progress and allowing the upload to
+/// be paused, which suits large files.
+pub fn upload_bytes_resumable(
+    ref_: &StorageReference,
+    data: &[u8],
+    metadata: Option<&UploadMetadataOptions>,
+) -> Result<UploadTask, StorageError> {
+    let metadata = metadata_to_value(metadata)?;
 
-    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        // Update waker
-        *self.waker.borrow_mut() = Some(cx.waker().to_owned());
+    b::upload_bytes_resumable(ref_, data, metadata)
+        .map(UploadTask::new)
+        .map_err(Into::into)
+}
 
-        if *self.completed.borrow() {
-            if let Some(err) = self.err.borrow_mut().take() {
-                Poll::Ready(Some(Err(err)))
-            } else {
-                Poll::Ready(None)
-            }
-        } else if let Some(snapshot) = self.snapshot.borrow_mut().take() {
-            Poll::Ready(Some(Ok(snapshot)))
-        } else {
-            Poll::Pending
-        }
-    }
+/// Like [`upload_bytes_resumable`], for a [`web_sys::Blob`] or
+/// `File`, without copying it into wasm memory.
+pub fn upload_blob_resumable(
+    ref_: &StorageReference,
+    data: &web_sys::Blob,
+    metadata: Option<&UploadMetadataOptions>,
+) -> Result<UploadTask, StorageError> {
+    let metadata = metadata_to_value(metadata)?;
+
+    b::upload_blob_resumable(ref_, data, metadata)
+        .map(UploadTask::new)
+        .map_err(Into::into)
 }
 
-pub async fn get_metadata(ref_: Ref) -> Result<FullMetadata, JsValue> {
-    bindings::get_metadata(ref_)
+pub async fn get_metadata(ref_: &StorageReference) -> Result<FullMetadata, StorageError> {
+    b::get_metadata(ref_)
+        .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
+        .map(|m| m.unchecked_into())
+}
+
+/// Updates the fields of the object's metadata that are set in `metadata`,
+/// returning the resulting metadata.
+pub async fn update_metadata(
+    ref_: &StorageReference,
+    metadata: &SettableMetadataOptions,
+) -> Result<FullMetadata, StorageError> {
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+
+    let metadata = metadata
+        .serialize(&serializer)
+        .map_err(StorageError::serialization)?;
+
+    b::update_metadata(ref_, metadata)
         .await
+        .map_err(|err| err.unchecked_into::<FirebaseError>().into())
         .map(|m| m.unchecked_into())
 }
@@ -1,3 +1,4 @@
+use crate::{app::FirebaseApp, FirebaseError};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
@@ -25,70 +26,222 @@ impl UploadMetadataOptions {
     }
 }
 
+/// Metadata to change with [`update_metadata`](super::update_metadata).
+///
+/// Fields left as `None` are not changed.
+#[derive(Debug, Clone, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(strip_option, into)))]
+pub struct SettableMetadataOptions {
+    pub cache_control: Option<String>,
+    pub content_disposition: Option<String>,
+    pub content_encoding: Option<String>,
+    pub content_language: Option<String>,
+    pub content_type: Option<String>,
+    #[builder(default, setter(skip))]
+    custom_metadata: Option<HashMap<String, String>>,
+}
+
+impl SettableMetadataOptions {
+    /// Custom metadata replaces all of the object's existing custom metadata.
+    pub fn add_custom_metadata(mut self, key: impl ToString, value: impl ToString) -> Self {
+        self.custom_metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.to_string());
+
+        self
+    }
+}
+
+impl SettableMetadata {
+    /// Custom key-value pairs, such as an owner's ID.
+    pub fn custom_metadata(&self) -> HashMap<String, String> {
+        self.custom_metadata_js()
+            .and_then(|o| serde_wasm_bindgen::from_value(o.into()).ok())
+            .unwrap_or_default()
+    }
+}
+
 impl UploadMetadata {
     pub fn get_custom_metadata<T>(&self) -> Result<Option<T>, serde_wasm_bindgen::Error>
     where
         T: for<'de> Deserialize<'de>,
     {
-        self.custom_metadata()
+        self.custom_metadata_js()
             .map(|o| serde_wasm_bindgen::from_value::<T>(o.into()))
             .transpose()
     }
 }
 
-pub fn upload_bytes(
-    ref_: Ref,
-    data: &web_sys::Blob,
-    metadata: Option<UploadMetadataOptions>,
-) -> Result<UploadTask, JsValue> {
-    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
-
-    let metadata = metadata.serialize(&serializer).unwrap();
-
-    upload_bytes_(ref_, data, metadata)
+impl FullMetadata {
+    /// The object's size, in bytes.
+    pub fn size(&self) -> u64 {
+        self.size_js() as u64
+    }
 }
 
 #[wasm_bindgen(module = "firebase/storage")]
 extern "C" {
-    pub type Storage;
-    pub type Ref;
-    pub type UploadTask;
+    #[derive(Clone, Debug)]
+    pub type FirebaseStorage;
+    #[derive(Clone, Debug)]
+    pub type StorageReference;
+    #[derive(Clone, Debug)]
+    pub type UploadResult;
+    #[derive(Clone, Debug)]
+    pub type ListResult;
+    pub(crate) type UploadTask;
     pub type UploadTaskSnapshot;
     pub type SettableMetadata;
     #[wasm_bindgen(extends = SettableMetadata)]
     pub type UploadMetadata;
     #[wasm_bindgen(extends = UploadMetadata)]
     pub type FullMetadata;
-    pub type TaskState;
 
+    /// Returns the [`FirebaseStorage`] instance of `app`, or of the default
+    /// app when `None`.
     #[wasm_bindgen(js_name = getStorage)]
-    pub fn get_storage() -> Storage;
+    pub fn get_storage(app: Option<&FirebaseApp>) -> FirebaseStorage;
 
+    /// Returns a reference to the object at `path`, such as
+    /// `"images/cat.png"`.
     #[wasm_bindgen(js_name = ref)]
-    pub fn ref_(storage: Storage, path: &str) -> Ref;
+    pub fn storage_ref(storage: &FirebaseStorage, path: &str) -> StorageReference;
+
+    #[wasm_bindgen(js_name = ref)]
+    pub(crate) fn child_ref(parent: &StorageReference, path: &str) -> StorageReference;
+
+    /// Connects to the Storage emulator at `host` and `port`, such as
+    /// `"127.0.0.1"` and `9199`.
+    ///
+    /// Must be called before any other storage operation.
+    #[wasm_bindgen(js_name = connectStorageEmulator)]
+    pub fn connect_storage_emulator(storage: &FirebaseStorage, host: &str, port: u16);
+
+    #[wasm_bindgen(js_name = uploadBytes, catch)]
+    pub(crate) async fn upload_bytes(
+        ref_: &StorageReference,
+        data: &[u8],
+        metadata: JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = uploadBytes, catch)]
+    pub(crate) async fn upload_blob(
+        ref_: &StorageReference,
+        data: &web_sys::Blob,
+        metadata: JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = uploadString, catch)]
+    pub(crate) async fn upload_string(
+        ref_: &StorageReference,
+        value: &str,
+        format: &str,
+        metadata: JsValue,
+    ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(js_name = uploadBytesResumable, catch)]
-    fn upload_bytes_(
-        ref_: Ref,
+    pub(crate) fn upload_bytes_resumable(
+        ref_: &StorageReference,
+        data: &[u8],
+        metadata: JsValue,
+    ) -> Result<UploadTask, FirebaseError>;
+
+    #[wasm_bindgen(js_name = uploadBytesResumable, catch)]
+    pub(crate) fn upload_blob_resumable(
+        ref_: &StorageReference,
         data: &web_sys::Blob,
         metadata: JsValue,
-    ) -> Result<UploadTask, JsValue>;
+    ) -> Result<UploadTask, FirebaseError>;
 
     #[wasm_bindgen(js_name = getDownloadURL, catch)]
-    pub async fn get_download_url(ref_: Ref) -> Result<JsValue, JsValue>;
+    pub(crate) async fn get_download_url(ref_: &StorageReference) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getBytes, catch)]
+    pub(crate) async fn get_bytes(
+        ref_: &StorageReference,
+        max_download_size_bytes: Option<f64>,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getBlob, catch)]
+    pub(crate) async fn get_blob(
+        ref_: &StorageReference,
+        max_download_size_bytes: Option<f64>,
+    ) -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(js_name = deleteObject, catch)]
-    pub async fn delete_object(ref_: Ref) -> Result<(), JsValue>;
+    pub(crate) async fn delete_object(ref_: &StorageReference) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(js_name = listAll, catch)]
+    pub(crate) async fn list_all(ref_: &StorageReference) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch)]
+    pub(crate) async fn list(ref_: &StorageReference, options: JsValue)
+        -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(js_name = getMetadata, catch)]
-    pub async fn get_metadata(ref_: Ref) -> Result<JsValue, JsValue>;
+    pub(crate) async fn get_metadata(ref_: &StorageReference) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = updateMetadata, catch)]
+    pub(crate) async fn update_metadata(
+        ref_: &StorageReference,
+        metadata: JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    // =========================================================================
+    //                           StorageReference
+    // =========================================================================
+
+    /// The last segment of the path, such as `"cat.png"`.
+    #[wasm_bindgen(method, getter)]
+    pub fn name(this: &StorageReference) -> String;
+
+    /// The path within the bucket, such as `"images/cat.png"`.
+    #[wasm_bindgen(method, getter, js_name = fullPath)]
+    pub fn full_path(this: &StorageReference) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn bucket(this: &StorageReference) -> String;
+
+    /// The reference one segment up, or `None` for the root.
+    #[wasm_bindgen(method, getter)]
+    pub fn parent(this: &StorageReference) -> Option<StorageReference>;
+
+    /// The root of the bucket.
+    #[wasm_bindgen(method, getter)]
+    pub fn root(this: &StorageReference) -> StorageReference;
+
+    // =========================================================================
+    //                             UploadResult
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter)]
+    pub fn metadata(this: &UploadResult) -> FullMetadata;
+
+    #[wasm_bindgen(method, getter, js_name = ref)]
+    pub fn ref_(this: &UploadResult) -> StorageReference;
+
+    // =========================================================================
+    //                              ListResult
+    // =========================================================================
+
+    #[wasm_bindgen(method, getter, js_name = items)]
+    pub(crate) fn items_js(this: &ListResult) -> js_sys::Array;
+
+    #[wasm_bindgen(method, getter, js_name = prefixes)]
+    pub(crate) fn prefixes_js(this: &ListResult) -> js_sys::Array;
+
+    /// Pass to [`ListOptions::page_token`] to list the next page, or `None`
+    /// if this was the last page.
+    #[wasm_bindgen(method, getter, js_name = nextPageToken)]
+    pub fn next_page_token(this: &ListResult) -> Option<String>;
 
     // =========================================================================
     //                              UploadTask
     // =========================================================================
 
     #[wasm_bindgen(method)]
-    pub fn on(
+    pub(crate) fn on(
         this: &UploadTask,
         event: &str,
         on_snapshot: &Closure<dyn FnMut(UploadTaskSnapshot)>,
@@ -96,39 +249,36 @@ extern "C" {
         on_complete: Option<&Closure<dyn FnMut()>>,
     ) -> js_sys::Function;
 
-    #[wasm_bindgen(method)]
-    pub fn cancel(this: &UploadTask) -> bool;
+    #[wasm_bindgen(method, js_name = cancel)]
+    pub(crate) fn cancel_js(this: &UploadTask) -> bool;
 
-    #[wasm_bindgen(method)]
-    pub fn pause(this: &UploadTask) -> bool;
+    #[wasm_bindgen(method, js_name = pause)]
+    pub(crate) fn pause_js(this: &UploadTask) -> bool;
 
-    #[wasm_bindgen(method)]
-    pub fn resume(this: &UploadTask) -> bool;
+    #[wasm_bindgen(method, js_name = resume)]
+    pub(crate) fn resume_js(this: &UploadTask) -> bool;
 
     #[wasm_bindgen(method, getter)]
-    pub fn snapshot(this: &UploadTask) -> UploadTaskSnapshot;
+    pub(crate) fn snapshot(this: &UploadTask) -> UploadTaskSnapshot;
 
     // =========================================================================
     //                            UploadTaskSnapshot
     // =========================================================================
 
     #[wasm_bindgen(method, getter, js_name = bytesTransferred)]
-    pub fn bytes_transferred(this: &UploadTaskSnapshot) -> usize;
+    pub fn bytes_transferred(this: &UploadTaskSnapshot) -> f64;
 
     #[wasm_bindgen(method, getter, js_name = totalBytes)]
-    pub fn total_bytes(this: &UploadTaskSnapshot) -> usize;
+    pub fn total_bytes(this: &UploadTaskSnapshot) -> f64;
 
     #[wasm_bindgen(method, getter, js_name = ref)]
-    pub fn ref_(this: &UploadTaskSnapshot) -> Ref;
+    pub fn ref_(this: &UploadTaskSnapshot) -> StorageReference;
 
     #[wasm_bindgen(method, getter)]
     pub fn metadata(this: &UploadTaskSnapshot) -> FullMetadata;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn state(this: &UploadTaskSnapshot) -> String;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn task(this: &UploadTaskSnapshot) -> UploadTask;
+    #[wasm_bindgen(method, getter, js_name = state)]
+    pub(crate) fn state_js(this: &UploadTaskSnapshot) -> String;
 
     // =========================================================================
     //                            SettableMetadata
@@ -150,7 +300,7 @@ extern "C" {
     pub fn content_type(this: &SettableMetadata) -> Option<String>;
 
     #[wasm_bindgen(method, getter, js_name = customMetadata)]
-    pub fn custom_metadata(this: &SettableMetadata) -> Option<js_sys::Object>;
+    pub(crate) fn custom_metadata_js(this: &SettableMetadata) -> Option<js_sys::Object>;
 
     // =========================================================================
     //                            UploadMetadata
@@ -178,8 +328,8 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn name(this: &FullMetadata) -> String;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn size(this: &FullMetadata) -> u64;
+    #[wasm_bindgen(method, getter, js_name = size)]
+    pub(crate) fn size_js(this: &FullMetadata) -> f64;
 
     #[wasm_bindgen(method, getter, js_name = timeCreated)]
     pub fn time_created(this: &FullMetadata) -> String;
@@ -191,5 +341,5 @@ extern "C" {
     pub fn download_tokens(this: &FullMetadata) -> Vec<js_sys::JsString>;
 
     #[wasm_bindgen(method, getter, js_name = ref)]
-    pub fn ref_(this: &FullMetadata) -> Option<Ref>;
+    pub fn ref_(this: &FullMetadata) -> Option<StorageReference>;
 }
@@ -0,0 +1,83 @@
+use crate::{FirebaseError, Unsubscribe};
+use futures::{channel::mpsc, Stream, StreamExt};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use wasm_bindgen::{convert::FromWasmAbi, prelude::*};
+
+/// Stream of the values sent by a JS listener, which removes the listener
+/// when dropped.
+pub(crate) struct CallbackStream<T> {
+    rx: mpsc::UnboundedReceiver<T>,
+    _unsubscribe: Unsubscribe,
+}
+
+impl<T> CallbackStream<T> {
+    /// For listeners that [`callback_stream`] and [`try_callback_stream`]
+    /// can't register, such as those with several arguments. `unsubscribe`
+    /// must own the closures sending to `rx`.
+    pub(crate) fn new(rx: mpsc::UnboundedReceiver<T>, unsubscribe: Unsubscribe) -> Self {
+        Self {
+            rx,
+            _unsubscribe: unsubscribe,
+        }
+    }
+}
+
+impl<T> Stream for CallbackStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
+/// Registers a listener with `register`, which must return the function
+/// removing it, and yields each value it is called with after `map`.
+pub(crate) fn callback_stream<A, T>(
+    register: impl FnOnce(&Closure<dyn FnMut(A)>) -> js_sys::Function,
+    mut map: impl FnMut(A) -> T + 'static,
+) -> CallbackStream<T>
+where
+    A: FromWasmAbi + 'static,
+    T: 'static,
+{
+    let (tx, rx) = mpsc::unbounded();
+
+    let on_next = Closure::new(move |arg: A| {
+        tx.unbounded_send(map(arg)).ok();
+    });
+
+    let unsubscribe = register(&on_next);
+
+    CallbackStream::new(rx, Unsubscribe::new(unsubscribe, on_next))
+}
+
+/// Like [`callback_stream`], for listeners that also take an error callback,
+/// whose errors are yielded alongside the mapped values.
+pub(crate) fn try_callback_stream<A, T, E>(
+    register: impl FnOnce(
+        &Closure<dyn FnMut(A)>,
+        &Closure<dyn FnMut(FirebaseError)>,
+    ) -> js_sys::Function,
+    mut map: impl FnMut(A) -> Result<T, E> + 'static,
+) -> CallbackStream<Result<T, E>>
+where
+    A: FromWasmAbi + 'static,
+    T: 'static,
+    E: From<FirebaseError> + 'static,
+{
+    let (tx, rx) = mpsc::unbounded();
+
+    let on_next = Closure::new(clone!([tx], move |arg: A| {
+        tx.unbounded_send(map(arg)).ok();
+    }));
+    let on_error = Closure::new(move |err: FirebaseError| {
+        tx.unbounded_send(Err(err.into())).ok();
+    });
+
+    let unsubscribe = register(&on_next, &on_error);
+
+    CallbackStream::new(rx, Unsubscribe::new(unsubscribe, (on_next, on_error)))
+}
@@ -0,0 +1,76 @@
+//! These tests run against the Auth emulator, which must be listening on
+//! `http://127.0.0.1:9099`.
+
+use firebase_wasm::{
+    app::{initialize_app, FirebaseConfig},
+    auth::{self, Auth, EmailAuthProvider},
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Each test uses its own app, so signing in or out in one test doesn't
+/// affect the others.
+fn emulated_auth(app_name: &str) -> Auth {
+    let config = FirebaseConfig::builder()
+        .api_key("fake-api-key")
+        .auth_domain("demo-firebase-wasm.firebaseapp.com")
+        .project_id("demo-firebase-wasm")
+        .storage_bucket("demo-firebase-wasm.appspot.com")
+        .messaging_sender_id("0")
+        .app_id("0")
+        .build();
+
+    let app = initialize_app(&config, Some(app_name)).unwrap();
+    let auth = auth::get_auth(Some(&app));
+
+    auth::connect_auth_emulator(auth.clone(), "http://127.0.0.1:9099", true).unwrap();
+
+    auth
+}
+
+#[wasm_bindgen_test]
+async fn reauthenticate_allows_sensitive_operations() {
+    let auth = emulated_auth("reauthenticate");
+    let email = "reauthenticate@example.com";
+
+    let user = auth::create_user_with_email_and_password(
+        auth.clone(),
+        email.to_owned(),
+        "password".to_owned(),
+    )
+    .await
+    .unwrap()
+    .user();
+
+    let credential = EmailAuthProvider::credential(email, "password");
+    auth::reauthenticate_with_credential(&user, &credential)
+        .await
+        .unwrap();
+
+    // Fails with `RequiresRecentLogin` unless the reauth took effect.
+    auth::update_password(&user, "new-password").await.unwrap();
+
+    auth::delete_user(&user).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+fn current_user_is_none_when_signed_out() {
+    let auth = emulated_auth("current-user-signed-out");
+
+    assert!(auth.current_user().is_none());
+}
+
+#[wasm_bindgen_test]
+async fn current_user_is_some_when_signed_in() {
+    let auth = emulated_auth("current-user-signed-in");
+
+    let user = auth::sign_in_anonymously(auth.clone())
+        .await
+        .unwrap()
+        .user();
+
+    assert_eq!(auth.current_user().map(|user| user.uid()), Some(user.uid()));
+
+    auth::delete_user(&user).await.unwrap();
+}